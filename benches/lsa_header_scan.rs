@@ -0,0 +1,71 @@
+// Compare scanning LSA headers with the borrowed view against parsing them
+// into owned OspfLsaHeader. Run with `cargo bench --bench lsa_header_scan`.
+use std::hint::black_box;
+use std::net::Ipv4Addr;
+use std::time::Instant;
+
+use bytes::BytesMut;
+use ospf_packet::*;
+
+const HEADERS: usize = 10_000;
+const ROUNDS: u32 = 100;
+
+fn headers() -> Vec<u8> {
+    let mut buf = BytesMut::new();
+    for i in 0..HEADERS as u32 {
+        let adv_router = Ipv4Addr::from(0x0a00_0000 | (i % 16));
+        let mut h = OspfLsaHeader::new(OspfLsType::Router, Ipv4Addr::from(i), adv_router);
+        h.ls_seq_number = 0x8000_0001 + i;
+        h.length = 20;
+        h.emit(&mut buf);
+    }
+    buf.to_vec()
+}
+
+fn scan_view(input: &[u8], adv_router: Ipv4Addr) -> u32 {
+    let mut max = 0;
+    for chunk in input.chunks_exact(OSPF_LSA_HEADER_LEN) {
+        let h = parse_lsa_header_view(chunk).unwrap();
+        if h.adv_router() == adv_router {
+            max = max.max(h.ls_seq_number());
+        }
+    }
+    max
+}
+
+fn scan_owned(input: &[u8], adv_router: Ipv4Addr) -> u32 {
+    let mut max = 0;
+    for chunk in input.chunks_exact(OSPF_LSA_HEADER_LEN) {
+        let (_, h) = parse_lsa_header(chunk).unwrap();
+        if h.adv_router == adv_router {
+            max = max.max(h.ls_seq_number);
+        }
+    }
+    max
+}
+
+fn bench(name: &str, input: &[u8], scan: fn(&[u8], Ipv4Addr) -> u32) {
+    let adv_router = Ipv4Addr::new(10, 0, 0, 1);
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        black_box(scan(black_box(input), adv_router));
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{:<6} {:>8.1} ns/header",
+        name,
+        elapsed.as_nanos() as f64 / (ROUNDS as usize * HEADERS) as f64
+    );
+}
+
+fn main() {
+    let input = headers();
+    let adv_router = Ipv4Addr::new(10, 0, 0, 1);
+    assert_eq!(
+        scan_view(&input, adv_router),
+        scan_owned(&input, adv_router)
+    );
+
+    bench("view", &input, scan_view);
+    bench("owned", &input, scan_owned);
+}
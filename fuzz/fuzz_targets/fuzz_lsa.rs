@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Parse errors are ignored, only panics are reported.
+fuzz_target!(|data: &[u8]| {
+    if let Ok((_, lsa)) = ospf_packet::parse_lsa(data) {
+        let _ = lsa.emit_to_vec();
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Parse errors are ignored, only panics are reported.
+fuzz_target!(|data: &[u8]| {
+    if let Ok((_, packet)) = ospf_packet::parse(data) {
+        let _ = packet.emit_to_vec();
+    }
+    let _ = ospf_packet::parse_validated(data);
+});
@@ -0,0 +1,108 @@
+use std::convert::TryFrom;
+use std::net::Ipv4Addr;
+
+use proptest::arbitrary::{any, Arbitrary};
+use proptest::collection::vec;
+use proptest::prop_oneof;
+use proptest::strategy::{BoxedStrategy, Strategy};
+
+use super::*;
+
+// Arbitrary values for property based tests of downstream crates, enabled by
+// the "proptest" feature. Values are generated so that emit() followed by
+// parse() gives the same value back.
+
+impl Arbitrary for OspfHello {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<Ipv4Addr>(),
+            any::<u16>(),
+            any::<u8>(),
+            any::<u8>(),
+            any::<u32>(),
+            any::<Ipv4Addr>(),
+            any::<Ipv4Addr>(),
+            vec(any::<Ipv4Addr>(), 0..8),
+        )
+            .prop_map(
+                |(netmask, hello_interval, options, priority, dead, dr, bdr, neighbors)| {
+                    OspfHello {
+                        netmask,
+                        hello_interval,
+                        options: options.into(),
+                        priority,
+                        router_dead_interval: dead,
+                        d_router: dr,
+                        bd_router: bdr,
+                        neighbors,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+// LSA headers are left empty.
+impl Arbitrary for OspfDbDesc {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<u16>(), any::<u8>(), any::<u8>(), any::<u32>())
+            .prop_map(|(if_mtu, options, flags, seqnum)| OspfDbDesc {
+                if_mtu,
+                options: options.into(),
+                flags: flags.into(),
+                seqnum,
+                lsa_headers: Vec::new(),
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for OspfLsaHeader {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<u16>(),
+            any::<u8>(),
+            any::<u8>(),
+            any::<Ipv4Addr>(),
+            any::<Ipv4Addr>(),
+            any::<u32>(),
+            any::<u16>(),
+            any::<u16>(),
+        )
+            .prop_map(
+                |(ls_age, options, ls_type, ls_id, adv_router, seq, checksum, length)| {
+                    OspfLsaHeader {
+                        ls_age,
+                        options: options.into(),
+                        ls_type: OspfLsType::try_from(ls_type).unwrap_or_else(OspfLsType::Unknown),
+                        ls_id,
+                        adv_router,
+                        ls_seq_number: seq,
+                        ls_checksum: checksum,
+                        length,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+// Hello or Database Description packet. Length and checksum are left 0, they
+// are set by emit().
+pub fn ospfv2_packet() -> impl Strategy<Value = Ospfv2Packet> {
+    let payload = prop_oneof![
+        any::<OspfHello>().prop_map(Ospfv2Payload::Hello),
+        any::<OspfDbDesc>().prop_map(Ospfv2Payload::DbDesc),
+    ];
+    (any::<Ipv4Addr>(), any::<Ipv4Addr>(), payload)
+        .prop_map(|(router_id, area_id, payload)| Ospfv2Packet::new(&router_id, &area_id, payload))
+}
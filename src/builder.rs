@@ -0,0 +1,165 @@
+use std::net::Ipv4Addr;
+
+use super::{
+    DbDescFlags, OspfDbDesc, OspfHello, OspfLsaHeader, OspfOptions, RouterLsa, RouterLsaFlags,
+    RouterLsaLink,
+};
+
+#[derive(Debug, Default)]
+pub struct OspfHelloBuilder {
+    hello: OspfHello,
+}
+
+impl OspfHelloBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn netmask(mut self, netmask: Ipv4Addr) -> Self {
+        self.hello.netmask = netmask;
+        self
+    }
+
+    pub fn hello_interval(mut self, hello_interval: u16) -> Self {
+        self.hello.hello_interval = hello_interval;
+        self
+    }
+
+    pub fn options(mut self, options: OspfOptions) -> Self {
+        self.hello.options = options;
+        self
+    }
+
+    pub fn priority(mut self, priority: u8) -> Self {
+        self.hello.priority = priority;
+        self
+    }
+
+    pub fn router_dead_interval(mut self, router_dead_interval: u32) -> Self {
+        self.hello.router_dead_interval = router_dead_interval;
+        self
+    }
+
+    pub fn designated_router(mut self, d_router: Ipv4Addr) -> Self {
+        self.hello.d_router = d_router;
+        self
+    }
+
+    pub fn backup_designated_router(mut self, bd_router: Ipv4Addr) -> Self {
+        self.hello.bd_router = bd_router;
+        self
+    }
+
+    pub fn add_neighbor(mut self, neighbor: Ipv4Addr) -> Self {
+        self.hello.neighbors.push(neighbor);
+        self
+    }
+
+    pub fn build(self) -> OspfHello {
+        self.hello
+    }
+}
+
+impl OspfHello {
+    pub fn builder() -> OspfHelloBuilder {
+        OspfHelloBuilder::new()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct OspfDbDescBuilder {
+    dd: OspfDbDesc,
+}
+
+impl OspfDbDescBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn mtu(mut self, if_mtu: u16) -> Self {
+        self.dd.if_mtu = if_mtu;
+        self
+    }
+
+    pub fn options(mut self, options: OspfOptions) -> Self {
+        self.dd.options = options;
+        self
+    }
+
+    pub fn flags(mut self, flags: DbDescFlags) -> Self {
+        self.dd.flags = flags;
+        self
+    }
+
+    pub fn sequence_number(mut self, seqnum: u32) -> Self {
+        self.dd.seqnum = seqnum;
+        self
+    }
+
+    pub fn add_lsa_header(mut self, lsa_header: OspfLsaHeader) -> Self {
+        self.dd.lsa_headers.push(lsa_header);
+        self
+    }
+
+    pub fn build(self) -> OspfDbDesc {
+        self.dd
+    }
+}
+
+impl OspfDbDesc {
+    pub fn builder() -> OspfDbDescBuilder {
+        OspfDbDescBuilder::new()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RouterLsaBuilder {
+    lsa: RouterLsa,
+}
+
+impl RouterLsaBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn flags(mut self, flags: RouterLsaFlags) -> Self {
+        self.lsa.flags = flags;
+        self
+    }
+
+    pub fn add_stub_link(self, prefix: Ipv4Addr, mask: Ipv4Addr, metric: u16) -> Self {
+        self.add_link(RouterLsaLink::new_stub(prefix, mask, metric))
+    }
+
+    pub fn add_transit_link(self, dr_ip: Ipv4Addr, router_ip: Ipv4Addr, metric: u16) -> Self {
+        self.add_link(RouterLsaLink::new_transit(dr_ip, router_ip, metric))
+    }
+
+    pub fn add_p2p_link(self, neighbor_id: Ipv4Addr, local_ip: Ipv4Addr, metric: u16) -> Self {
+        self.add_link(RouterLsaLink::new_point_to_point(
+            neighbor_id,
+            local_ip,
+            metric,
+        ))
+    }
+
+    pub fn add_virtual_link(self, neighbor_id: Ipv4Addr, local_ip: Ipv4Addr, metric: u16) -> Self {
+        self.add_link(RouterLsaLink::new_virtual(neighbor_id, local_ip, metric))
+    }
+
+    fn add_link(mut self, link: RouterLsaLink) -> Self {
+        self.lsa.links.push(link);
+        self
+    }
+
+    pub fn build(mut self) -> RouterLsa {
+        self.lsa.num_links = self.lsa.links.len() as u16;
+        self.lsa
+    }
+}
+
+impl RouterLsa {
+    pub fn builder() -> RouterLsaBuilder {
+        RouterLsaBuilder::new()
+    }
+}
@@ -0,0 +1,39 @@
+use std::net::Ipv4Addr;
+
+use super::{OspfType, Ospfv2Packet};
+
+// IP protocol number of OSPF.
+pub const OSPF_IP_PROTOCOL: u8 = 89;
+
+// AllSPFRouters multicast address (RFC 2328 Appendix A.1).
+pub const ALL_SPF_ROUTERS: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 5);
+
+// AllDRouters multicast address (RFC 2328 Appendix A.1).
+pub const ALL_D_ROUTERS: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 6);
+
+// OSPF packet header length, which is also the minimum packet length.
+pub const OSPF_MIN_PACKET_LEN: usize = 24;
+
+// OSPF packet header and the fixed fields of Hello.
+pub const OSPF_HELLO_MIN_LEN: usize = 44;
+
+// LSA header length.
+pub const OSPF_LSA_HEADER_LEN: usize = 20;
+
+// Maximum packet length, limited by the 16-bit packet length field.
+pub const OSPF_MAX_PACKET_LEN: usize = 65535;
+
+// Multicast destination of the packet based on the packet type. Hello is
+// always sent to AllSPFRouters, the other packets are sent to AllDRouters.
+// This is a heuristic since the actual destination also depends on the
+// interface type and the DR state of the sender (e.g. LS Update flooded by
+// the DR is sent to AllSPFRouters, Database Description and LS Request are
+// unicast on most interfaces).
+pub fn ospf_multicast_dest(packet: &Ospfv2Packet) -> Ipv4Addr {
+    match packet.typ {
+        OspfType::DbDesc | OspfType::LsRequest | OspfType::LsUpdate | OspfType::LsAck => {
+            ALL_D_ROUTERS
+        }
+        _ => ALL_SPF_ROUTERS,
+    }
+}
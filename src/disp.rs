@@ -1,4 +1,4 @@
-use std::fmt::{Display, Formatter, Result};
+use std::fmt::{Debug, Display, Formatter, Result};
 
 use super::*;
 
@@ -25,13 +25,57 @@ impl Display for Ospfv2Packet {
             self.auth_type,
             self.auth,
             self.payload,
-        )
+        )?;
+        if let Some(lls) = &self.lls {
+            write!(f, "\n{}", lls)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for OspfLlsBlock {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            r#"== LLS ==
+ Checksum: {:x}
+ Length: {}"#,
+            self.checksum, self.length
+        )?;
+        for tlv in self.tlvs.iter() {
+            match tlv.value() {
+                OspfLlsTlvValue::ExtendedOptions(v) => write!(f, "\n Extended options: {:08x}", v)?,
+                OspfLlsTlvValue::CryptAuth {
+                    sequence_number, ..
+                } => write!(
+                    f,
+                    "\n Cryptographic authentication sequence {}",
+                    sequence_number
+                )?,
+                OspfLlsTlvValue::Unknown => {
+                    write!(f, "\n Unknown TLV type {} length {}", tlv.typ, tlv.length)?
+                }
+            }
+        }
+        Ok(())
     }
 }
 
 impl Display for Ospfv2Auth {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(f, "{:x}", self.auth)
+        match self {
+            Ospfv2Auth::None => write!(f, "None"),
+            Ospfv2Auth::SimplePassword(pw) => write!(
+                f,
+                "Simple password {}",
+                String::from_utf8_lossy(pw).trim_end_matches('\0')
+            ),
+            Ospfv2Auth::Cryptographic(v) => write!(
+                f,
+                "Cryptographic key ID {} length {} sequence {}",
+                v.key_id, v.auth_data_len, v.seq
+            ),
+        }
     }
 }
 
@@ -43,6 +87,7 @@ impl Display for Ospfv2Payload {
             DbDesc(v) => write!(f, "{}", v),
             LsRequest(v) => write!(f, "{}", v),
             LsUpdate(v) => write!(f, "{}", v),
+            LsAck(v) => write!(f, "{}", v),
             _ => write!(f, "XXX Payload"),
         }
     }
@@ -75,29 +120,63 @@ impl Display for OspfHello {
     }
 }
 
+// Set option bits by name, comma-separated.
 impl Display for OspfOptions {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(
-            f,
-            r#"
-  DN: {}
-  O: {}
-  Demand Circuit: {}
-  LLS Data: {}
-  NSSA: {}
-  Multicast: {}
-  External: {}
-  MultiTopology: {}"#,
-            if self.dn() { "On" } else { "Off" },
-            if self.o() { "On" } else { "Off" },
-            if self.demand_circuits() { "On" } else { "Off" },
-            if self.lls_data() { "On" } else { "Off" },
-            if self.nssa() { "On" } else { "Off" },
-            if self.multicast() { "On" } else { "Off" },
-            if self.external() { "On" } else { "Off" },
-            if self.multi_toplogy() { "On" } else { "Off" },
-        )?;
-        Ok(())
+        let mut flags = Vec::new();
+        if self.multi_toplogy() {
+            flags.push("MT");
+        }
+        if self.external() {
+            flags.push("E");
+        }
+        if self.multicast() {
+            flags.push("MC");
+        }
+        if self.nssa() {
+            flags.push("N");
+        }
+        if self.lls_data() {
+            flags.push("LLS");
+        }
+        if self.demand_circuits() {
+            flags.push("DC");
+        }
+        if self.o() {
+            flags.push("O");
+        }
+        if self.dn() {
+            flags.push("DN");
+        }
+        if flags.is_empty() {
+            write!(f, "None")
+        } else {
+            write!(f, "{}", flags.join(","))
+        }
+    }
+}
+
+// Set flag bits by name, comma-separated.
+impl Display for DbDescFlags {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let mut flags = Vec::new();
+        if self.master() {
+            flags.push("MS");
+        }
+        if self.more() {
+            flags.push("M");
+        }
+        if self.init() {
+            flags.push("I");
+        }
+        if self.oob_resync() {
+            flags.push("OOB");
+        }
+        if flags.is_empty() {
+            write!(f, "None")
+        } else {
+            write!(f, "{}", flags.join(","))
+        }
     }
 }
 
@@ -108,14 +187,11 @@ impl Display for OspfDbDesc {
             r#"== Database Description ==
  Interface MTU: {}
  Options: {}
- Flags: master:{}, more:{}, init:{}, oob:{}
+ Flags: {}
  DD sequence number: {:04x}"#,
             self.if_mtu,
             self.options,
-            self.flags.master() as u8,
-            self.flags.more() as u8,
-            self.flags.init() as u8,
-            self.flags.oob_resync() as u8,
+            self.flags.as_str_flags(),
             self.seqnum,
         )?;
         for lsa in self.lsa_headers.iter() {
@@ -134,8 +210,8 @@ impl Display for OspfLsaHeader {
   LS Type: {}
   LS ID: {}
   Advertising router: {}
-  LS seq num: {:x}
-  LS checksu: {:?}
+  LS seq num: {:08x}
+  LS checksum: {:04x}
   Length: {}"#,
             self.ls_age,
             self.options,
@@ -149,6 +225,24 @@ impl Display for OspfLsaHeader {
     }
 }
 
+impl Debug for OspfLsaHeader {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("OspfLsaHeader")
+            .field("ls_age", &format_args!("{}s", self.ls_age))
+            .field("options", &format_args!("{}", self.options))
+            .field("ls_type", &format_args!("{}", self.ls_type))
+            .field("ls_id", &format_args!("{}", self.ls_id))
+            .field("adv_router", &format_args!("{}", self.adv_router))
+            .field(
+                "ls_seq_number",
+                &format_args!("0x{:08x}", self.ls_seq_number),
+            )
+            .field("ls_checksum", &format_args!("0x{:04x}", self.ls_checksum))
+            .field("length", &self.length)
+            .finish()
+    }
+}
+
 impl Display for OspfLsRequest {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         write!(f, r#"== Link State Request =="#,)?;
@@ -164,9 +258,11 @@ impl Display for OspfLsRequestEntry {
         write!(
             f,
             r#" LS Type: {}
-  LS ID: {:?}
+  LS ID: {}
   Advertising router: {}"#,
-            self.ls_type, self.ls_id, self.adv_router
+            self.typed_ls_type(),
+            self.ls_id_addr(),
+            self.adv_router
         )
     }
 }
@@ -186,16 +282,32 @@ impl Display for OspfLsUpdate {
     }
 }
 
+impl Display for OspfLsAck {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, r#"== Link State Acknowledgement =="#)?;
+        for h in self.lsa_headers.iter() {
+            write!(f, "\n{}", h)?;
+        }
+        Ok(())
+    }
+}
+
 impl Display for OspfLsa {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         use OspfLsaPayload::*;
-        write!(f, "{}", self.h).unwrap();
+        write!(f, "{}", self.h)?;
         match &self.lsa {
             Router(v) => write!(f, "\n{}", v),
             Network(v) => write!(f, "\n{}", v),
             AsExternal(v) => write!(f, "\n{}", v),
-            Unknown(_v) => write!(f, "Unknown"),
-            _ => write!(f, ""),
+            Summary(v) | SummaryAsbr(v) => write!(f, "\n{}", v),
+            NssaAsExternal(v) => write!(f, "\n{}", v),
+            OpaqueLink(v) => match self.lsa.as_grace_lsa() {
+                Some(grace) => write!(f, "\n{}\n{}", v, grace),
+                None => write!(f, "\n{}", v),
+            },
+            OpaqueArea(v) | OpaqueAs(v) => write!(f, "\n{}", v),
+            Unknown(v) => write!(f, "\n{}", v),
         }
     }
 }
@@ -216,6 +328,35 @@ impl Display for RouterLsa {
     }
 }
 
+impl Display for RouterLsaFlags {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let mut flags = Vec::new();
+        if self.abr() {
+            flags.push("ABR");
+        }
+        if self.asbr() {
+            flags.push("ASBR");
+        }
+        if self.virtual_endpoint() {
+            flags.push("VL-endpoint");
+        }
+        if self.wildcard_multicast() {
+            flags.push("W");
+        }
+        if self.nssa_translator() {
+            flags.push("NSSA-translator");
+        }
+        if self.shortcut_abr() {
+            flags.push("Shortcut-ABR");
+        }
+        if flags.is_empty() {
+            write!(f, "None")
+        } else {
+            write!(f, "{}", flags.join(" "))
+        }
+    }
+}
+
 impl Display for RouterLsaLink {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         write!(
@@ -252,17 +393,149 @@ impl Display for NetworkLsa {
     }
 }
 
+impl Display for SummaryLsa {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            r#"== Summary LSA ==
+  Netmask: {}
+  Metric: {}"#,
+            self.netmask, self.metric
+        )?;
+        for tos in self.tos_routes.iter() {
+            write!(f, "\n{}", tos)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for TosRoute {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "  ToS: {} Metric: {}", self.tos, self.metric)
+    }
+}
+
 impl Display for AsExternalLsa {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         write!(
             f,
             r#"== AS External LSA ==
-  Forwarding Address: {}"#,
-            self.forwarding_address
+  Netmask: {}
+  Metric type: {}
+  Metric: {}
+  Forwarding Address: {}
+  External Route Tag: {}"#,
+            self.netmask,
+            self.metric_type(),
+            self.metric,
+            self.forwarding_address,
+            self.external_route_tag,
+        )
+    }
+}
+
+impl Display for AsExternalMetricType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            AsExternalMetricType::Type1 => write!(f, "1"),
+            AsExternalMetricType::Type2 => write!(f, "2"),
+        }
+    }
+}
+
+impl Display for NssaAsExternalLsa {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            r#"== NSSA AS External LSA ==
+  Netmask: {}
+  Metric type: {}
+  Metric: {}
+  Forwarding Address: {}
+  External Route Tag: {}"#,
+            self.netmask,
+            if self.is_type2_metric() { 2 } else { 1 },
+            self.metric,
+            self.forwarding_address,
+            self.external_route_tag,
+        )
+    }
+}
+
+impl Display for OpaqueLsa {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            r#"== Opaque LSA ==
+  Opaque type: {}
+  Opaque ID: {}
+  Data length: {}"#,
+            self.opaque_type,
+            self.opaque_id,
+            self.data.len(),
+        )
+    }
+}
+
+impl Display for UnknownLsa {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            r#"== Unknown LSA ==
+  Length: {}"#,
+            self.data.len()
         )?;
-        // for link in self.links.iter() {
-        //     write!(f, "\n{}", link)?;
-        // }
+        // Same layout as "hexdump -C".
+        for (i, chunk) in self.data.chunks(16).enumerate() {
+            write!(f, "\n  {:08x}  ", i * 16)?;
+            for j in 0..16 {
+                match chunk.get(j) {
+                    Some(b) => write!(f, "{:02x} ", b)?,
+                    None => write!(f, "   ")?,
+                }
+                if j == 7 {
+                    write!(f, " ")?;
+                }
+            }
+            write!(f, " |")?;
+            for &b in chunk.iter() {
+                let c = if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                };
+                write!(f, "{}", c)?;
+            }
+            write!(f, "|")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for GraceReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        use GraceReason::*;
+        match self {
+            UnknownReason => write!(f, "Unknown"),
+            SoftwareRestart => write!(f, "Software restart"),
+            SoftwareUpgrade => write!(f, "Software reload/upgrade"),
+            ControlPlaneRestart => write!(f, "Switch to redundant control processor"),
+        }
+    }
+}
+
+impl Display for GraceLsa {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            r#"== Grace LSA ==
+  Grace period: {}
+  Reason: {}"#,
+            self.grace_period, self.reason
+        )?;
+        if let Some(addr) = self.ip_address {
+            write!(f, "\n  IP interface address: {}", addr)?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,52 @@
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum OspfError {
+    Checksum { expected: u16, actual: u16 },
+    Length { expected: usize, actual: usize },
+    UnknownVersion(u8),
+    InvalidAuthType(u16),
+    Truncated,
+    ParseFailed(String),
+}
+
+impl Display for OspfError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OspfError::Checksum { expected, actual } => {
+                write!(
+                    f,
+                    "Checksum mismatch: expected 0x{:04x}, actual 0x{:04x}",
+                    expected, actual
+                )
+            }
+            OspfError::Length { expected, actual } => {
+                write!(
+                    f,
+                    "Length mismatch: expected {}, actual {}",
+                    expected, actual
+                )
+            }
+            OspfError::UnknownVersion(version) => write!(f, "Unknown version {}", version),
+            OspfError::InvalidAuthType(auth_type) => {
+                write!(f, "Invalid auth type {}", auth_type)
+            }
+            OspfError::Truncated => write!(f, "Truncated packet"),
+            OspfError::ParseFailed(msg) => write!(f, "Parse failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for OspfError {}
+
+impl From<nom::Err<nom::error::Error<&[u8]>>> for OspfError {
+    fn from(err: nom::Err<nom::error::Error<&[u8]>>) -> Self {
+        match err {
+            nom::Err::Incomplete(_) => OspfError::Truncated,
+            nom::Err::Error(e) | nom::Err::Failure(e) => match e.code {
+                nom::error::ErrorKind::Eof => OspfError::Truncated,
+                code => OspfError::ParseFailed(code.description().to_string()),
+            },
+        }
+    }
+}
@@ -1,9 +1,27 @@
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+mod builder;
+mod constants;
 mod disp;
+mod error;
+mod link_type;
+mod lls;
 mod ls_type;
+mod opaque;
 mod parser;
+mod seqnum;
 mod typ;
 mod util;
+pub mod v3;
 
-pub use ls_type::OspfLsType;
+pub use builder::*;
+pub use constants::*;
+pub use error::OspfError;
+pub use link_type::RouterLsaLinkType;
+pub use lls::*;
+pub use ls_type::{FloodingScope, OspfLsType};
+pub use opaque::*;
 pub use parser::*;
+pub use seqnum::SequenceNumber;
 pub use typ::OspfType;
+pub use util::Emit;
@@ -0,0 +1,66 @@
+use std::fmt::Display;
+
+use nom::number::complete::be_u8;
+use nom::IResult;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[repr(u8)]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RouterLsaLinkType {
+    PointToPoint = 1,
+    Transit = 2,
+    #[default]
+    Stub = 3,
+    Virtual = 4,
+    Unknown(u8),
+}
+
+impl Display for RouterLsaLinkType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use RouterLsaLinkType::*;
+        let str = match self {
+            PointToPoint => "Point-to-Point",
+            Transit => "Transit",
+            Stub => "Stub",
+            Virtual => "Virtual",
+            Unknown(_) => "Unknown",
+        };
+        write!(f, "{str}")
+    }
+}
+
+impl From<RouterLsaLinkType> for u8 {
+    fn from(typ: RouterLsaLinkType) -> Self {
+        use RouterLsaLinkType::*;
+        match typ {
+            PointToPoint => 1,
+            Transit => 2,
+            Stub => 3,
+            Virtual => 4,
+            Unknown(v) => v,
+        }
+    }
+}
+
+impl From<u8> for RouterLsaLinkType {
+    fn from(typ: u8) -> Self {
+        use RouterLsaLinkType::*;
+        match typ {
+            1 => PointToPoint,
+            2 => Transit,
+            3 => Stub,
+            4 => Virtual,
+            v => Unknown(v),
+        }
+    }
+}
+
+impl RouterLsaLinkType {
+    pub fn parse_be(input: &[u8]) -> IResult<&[u8], Self> {
+        let (input, typ) = be_u8(input)?;
+        let link_type: Self = typ.into();
+        Ok((input, link_type))
+    }
+}
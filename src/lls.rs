@@ -0,0 +1,177 @@
+use bytes::{BufMut, BytesMut};
+use internet_checksum::Checksum;
+use nom::bytes::complete::take;
+use nom::error::{make_error, ErrorKind};
+use nom::number::complete::be_u16;
+use nom::{Err, IResult};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::util::{many0, Emit};
+
+// LLS TLV types (RFC 5613 section 2.4 and 2.5).
+pub const LLS_EXTENDED_OPTIONS: u16 = 1;
+pub const LLS_CRYPT_AUTH: u16 = 2;
+
+// LLS data block (RFC 5613 section 2.2). It is appended after the OSPF packet
+// (and the authentication trailer) when the L-bit is set in the options.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OspfLlsBlock {
+    pub checksum: u16,
+    // Length of the LLS data block in 32-bit words including the header.
+    pub length: u16,
+    pub tlvs: Vec<OspfLlsTlv>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OspfLlsTlv {
+    pub typ: u16,
+    // Length of the value in bytes without the padding.
+    pub length: u16,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OspfLlsTlvValue {
+    ExtendedOptions(u32),
+    CryptAuth {
+        sequence_number: u32,
+        auth_data: Vec<u8>,
+    },
+    Unknown,
+}
+
+impl OspfLlsBlock {
+    pub fn new(tlvs: Vec<OspfLlsTlv>) -> Self {
+        let mut lls = Self {
+            checksum: 0,
+            length: 0,
+            tlvs,
+        };
+        lls.length = (lls.byte_len() / 4) as u16;
+        lls
+    }
+
+    // Length of the LLS data block in bytes.
+    pub fn byte_len(&self) -> usize {
+        4 + self.tlvs.iter().map(|tlv| tlv.byte_len()).sum::<usize>()
+    }
+
+    pub fn extended_options(&self) -> Option<u32> {
+        self.tlvs.iter().find_map(|tlv| match tlv.value() {
+            OspfLlsTlvValue::ExtendedOptions(v) => Some(v),
+            _ => None,
+        })
+    }
+}
+
+impl Emit for OspfLlsBlock {
+    fn emit(&self, buf: &mut BytesMut) {
+        let offset = buf.len();
+        buf.put_u16(0);
+        buf.put_u16((self.byte_len() / 4) as u16);
+        for tlv in self.tlvs.iter() {
+            tlv.emit(buf);
+        }
+
+        // Standard IP checksum of the LLS data block.
+        let mut cksum = Checksum::new();
+        cksum.add_bytes(&buf[offset..]);
+        buf[offset..offset + 2].copy_from_slice(&cksum.checksum());
+    }
+}
+
+impl OspfLlsTlv {
+    pub fn new(typ: u16, data: Vec<u8>) -> Self {
+        Self {
+            typ,
+            length: data.len() as u16,
+            data,
+        }
+    }
+
+    pub fn extended_options(options: u32) -> Self {
+        Self::new(LLS_EXTENDED_OPTIONS, options.to_be_bytes().to_vec())
+    }
+
+    // Length of the TLV in bytes including the padding.
+    pub fn byte_len(&self) -> usize {
+        4 + self.data.len().next_multiple_of(4)
+    }
+
+    pub fn value(&self) -> OspfLlsTlvValue {
+        match self.typ {
+            LLS_EXTENDED_OPTIONS if self.data.len() == 4 => {
+                let mut v = [0u8; 4];
+                v.copy_from_slice(&self.data);
+                OspfLlsTlvValue::ExtendedOptions(u32::from_be_bytes(v))
+            }
+            LLS_CRYPT_AUTH if self.data.len() >= 4 => {
+                let mut v = [0u8; 4];
+                v.copy_from_slice(&self.data[..4]);
+                OspfLlsTlvValue::CryptAuth {
+                    sequence_number: u32::from_be_bytes(v),
+                    auth_data: self.data[4..].to_vec(),
+                }
+            }
+            _ => OspfLlsTlvValue::Unknown,
+        }
+    }
+
+    pub fn parse_be(input: &[u8]) -> IResult<&[u8], Self> {
+        let (input, typ) = be_u16(input)?;
+        let (input, length) = be_u16(input)?;
+        let (input, data) = take(length as usize)(input)?;
+        // Value is padded to 32-bit boundary.
+        let padding = (4 - length as usize % 4) % 4;
+        let (input, _) = take(padding.min(input.len()))(input)?;
+        Ok((
+            input,
+            Self {
+                typ,
+                length,
+                data: data.to_vec(),
+            },
+        ))
+    }
+}
+
+impl Emit for OspfLlsTlv {
+    fn emit(&self, buf: &mut BytesMut) {
+        buf.put_u16(self.typ);
+        buf.put_u16(self.data.len() as u16);
+        buf.put(&self.data[..]);
+        buf.put_bytes(0, self.byte_len() - 4 - self.data.len());
+    }
+}
+
+// Parse LLS data block with the checksum validation. With cryptographic
+// authentication (auth type 2) the LLS checksum is not calculated and is
+// set to 0 (RFC 5613 section 2.2), so the check is skipped.
+pub fn parse_lls(input: &[u8], auth_type: u16) -> IResult<&[u8], OspfLlsBlock> {
+    let (rem, checksum) = be_u16(input)?;
+    let (rem, length) = be_u16(rem)?;
+    if length == 0 {
+        return Err(Err::Error(make_error(input, ErrorKind::LengthValue)));
+    }
+    let (rem, body) = take((length as usize - 1) * 4)(rem)?;
+    if auth_type != 2 {
+        let mut cksum = Checksum::new();
+        cksum.add_bytes(&input[..length as usize * 4]);
+        if cksum.checksum() != [0; 2] {
+            return Err(Err::Error(make_error(input, ErrorKind::Verify)));
+        }
+    }
+    let (_, tlvs) = many0(OspfLlsTlv::parse_be)(body)?;
+    Ok((
+        rem,
+        OspfLlsBlock {
+            checksum,
+            length,
+            tlvs,
+        },
+    ))
+}
@@ -1,11 +1,15 @@
+use std::convert::TryFrom;
 use std::fmt::Display;
 
 use nom::number::complete::be_u8;
 use nom::IResult;
 use nom_derive::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 #[repr(u8)]
-#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum OspfLsType {
     #[default]
     Router = 1,
@@ -13,10 +17,12 @@ pub enum OspfLsType {
     Summary = 3,
     SummaryAsbr = 4,
     AsExternal = 5,
+    GroupMembership = 6,
     NssaAsExternal = 7,
-    OpaqueLinkLocal = 9,
-    OpaqueAreaLocal = 10,
-    OpaqueAsWide = 11,
+    Unassigned = 8,
+    OpaqueLink = 9,
+    OpaqueArea = 10,
+    OpaqueAs = 11,
     Unknown(u8),
 }
 
@@ -27,13 +33,15 @@ impl Display for OspfLsType {
             Router => "Router",
             Network => "Network",
             Summary => "Summary",
-            SummaryAsbr => "Summary ASBR",
+            SummaryAsbr => "Summary (ASBR)",
             AsExternal => "AS External",
-            NssaAsExternal => "NSSA AS External",
-            OpaqueLinkLocal => "Opaque Link Local",
-            OpaqueAreaLocal => "Opaque Area Local",
-            OpaqueAsWide => "Opaque AS Wide",
-            Unknown(_) => "Unknown",
+            GroupMembership => "Group Membership",
+            NssaAsExternal => "NSSA",
+            Unassigned => "Unassigned",
+            OpaqueLink => "Opaque Link",
+            OpaqueArea => "Opaque Area",
+            OpaqueAs => "Opaque AS",
+            Unknown(v) => return write!(f, "Unknown({})", v),
         };
         write!(f, "{str}")
     }
@@ -48,10 +56,12 @@ impl From<OspfLsType> for u8 {
             Summary => 3,
             SummaryAsbr => 4,
             AsExternal => 5,
+            GroupMembership => 6,
             NssaAsExternal => 7,
-            OpaqueLinkLocal => 9,
-            OpaqueAreaLocal => 10,
-            OpaqueAsWide => 11,
+            Unassigned => 8,
+            OpaqueLink => 9,
+            OpaqueArea => 10,
+            OpaqueAs => 11,
             Unknown(v) => v,
         }
     }
@@ -64,20 +74,24 @@ impl From<OspfLsType> for u32 {
     }
 }
 
-impl From<u8> for OspfLsType {
-    fn from(typ: u8) -> Self {
+impl TryFrom<u8> for OspfLsType {
+    type Error = u8;
+
+    fn try_from(typ: u8) -> Result<Self, Self::Error> {
         use OspfLsType::*;
         match typ {
-            1 => Router,
-            2 => Network,
-            3 => Summary,
-            4 => SummaryAsbr,
-            5 => AsExternal,
-            7 => NssaAsExternal,
-            9 => OpaqueLinkLocal,
-            10 => OpaqueAreaLocal,
-            11 => OpaqueAsWide,
-            v => Unknown(v),
+            1 => Ok(Router),
+            2 => Ok(Network),
+            3 => Ok(Summary),
+            4 => Ok(SummaryAsbr),
+            5 => Ok(AsExternal),
+            6 => Ok(GroupMembership),
+            7 => Ok(NssaAsExternal),
+            8 => Ok(Unassigned),
+            9 => Ok(OpaqueLink),
+            10 => Ok(OpaqueArea),
+            11 => Ok(OpaqueAs),
+            v => Err(v),
         }
     }
 }
@@ -85,7 +99,33 @@ impl From<u8> for OspfLsType {
 impl OspfLsType {
     pub fn parse_be(input: &[u8]) -> IResult<&[u8], Self> {
         let (input, typ) = be_u8(input)?;
-        let ls_type: Self = typ.into();
+        let ls_type = Self::try_from(typ).unwrap_or_else(Self::Unknown);
         Ok((input, ls_type))
     }
+
+    pub fn is_opaque(&self) -> bool {
+        use OspfLsType::*;
+        matches!(self, OpaqueLink | OpaqueArea | OpaqueAs)
+    }
+
+    // RFC 2328 Section 12.1 and RFC 5250 Section 3.
+    pub fn flooding_scope(&self) -> FloodingScope {
+        use OspfLsType::*;
+        match self {
+            OpaqueLink => FloodingScope::LinkLocal,
+            Router | Network | Summary | SummaryAsbr | GroupMembership | NssaAsExternal
+            | OpaqueArea => FloodingScope::Area,
+            AsExternal | OpaqueAs => FloodingScope::As,
+            Unassigned | Unknown(_) => FloodingScope::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FloodingScope {
+    LinkLocal,
+    Area,
+    As,
+    Unknown,
 }
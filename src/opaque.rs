@@ -0,0 +1,227 @@
+use std::net::Ipv4Addr;
+
+use nom::bytes::complete::take;
+use nom::number::complete::{be_f32, be_u16, be_u32, be_u8};
+use nom::IResult;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::util::{many0, ParseBe};
+use super::{OpaqueLsa, OspfLsaPayload};
+
+// Opaque type of Traffic Engineering LSA (RFC 3630).
+pub const OPAQUE_TYPE_TE: u8 = 1;
+
+// Opaque type of Grace LSA (RFC 3623).
+pub const OPAQUE_TYPE_GRACE: u8 = 3;
+
+// Grace LSA TLV types (RFC 3623 Appendix A).
+const GRACE_TLV_PERIOD: u16 = 1;
+const GRACE_TLV_REASON: u16 = 2;
+const GRACE_TLV_IP_ADDRESS: u16 = 3;
+
+// TE LSA TLV types (RFC 3630 section 2.4).
+const TE_TLV_ROUTER_ADDRESS: u16 = 1;
+const TE_TLV_LINK: u16 = 2;
+
+// TE Link sub-TLV types (RFC 3630 section 2.5).
+const TE_LINK_TYPE: u16 = 1;
+const TE_LINK_ID: u16 = 2;
+const TE_LINK_LOCAL_ADDR: u16 = 3;
+const TE_LINK_REMOTE_ADDR: u16 = 4;
+const TE_LINK_METRIC: u16 = 5;
+const TE_LINK_MAX_BANDWIDTH: u16 = 6;
+const TE_LINK_MAX_RESV_BANDWIDTH: u16 = 7;
+const TE_LINK_UNRESV_BANDWIDTH: u16 = 8;
+const TE_LINK_ADMIN_GROUP: u16 = 9;
+
+// TLV in Opaque LSA. Value is padded to 32-bit boundary.
+fn parse_tlv(input: &[u8]) -> IResult<&[u8], (u16, &[u8])> {
+    let (input, typ) = be_u16(input)?;
+    let (input, length) = be_u16(input)?;
+    let (input, value) = take(length as usize)(input)?;
+    let padding = (4 - length as usize % 4) % 4;
+    let (input, _) = take(padding.min(input.len()))(input)?;
+    Ok((input, (typ, value)))
+}
+
+#[repr(u8)]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GraceReason {
+    #[default]
+    UnknownReason = 0,
+    SoftwareRestart = 1,
+    SoftwareUpgrade = 2,
+    ControlPlaneRestart = 3,
+}
+
+impl From<u8> for GraceReason {
+    fn from(val: u8) -> Self {
+        use GraceReason::*;
+        match val {
+            1 => SoftwareRestart,
+            2 => SoftwareUpgrade,
+            3 => ControlPlaneRestart,
+            _ => UnknownReason,
+        }
+    }
+}
+
+impl From<GraceReason> for u8 {
+    fn from(reason: GraceReason) -> Self {
+        reason as u8
+    }
+}
+
+// Grace LSA (RFC 3623). It is Opaque Link LSA with opaque type 3.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GraceLsa {
+    pub grace_period: u32,
+    pub reason: GraceReason,
+    pub ip_address: Option<Ipv4Addr>,
+}
+
+// Parse Grace LSA TLVs in Opaque LSA data. Unknown TLVs are skipped.
+pub fn parse_grace_lsa(opaque_data: &[u8]) -> IResult<&[u8], GraceLsa> {
+    let (input, tlvs) = many0(parse_tlv)(opaque_data)?;
+    let mut grace = GraceLsa::default();
+    for (typ, value) in tlvs {
+        match typ {
+            GRACE_TLV_PERIOD => {
+                let (_, period) = be_u32(value)?;
+                grace.grace_period = period;
+            }
+            GRACE_TLV_REASON => {
+                let (_, reason) = be_u8(value)?;
+                grace.reason = reason.into();
+            }
+            GRACE_TLV_IP_ADDRESS => {
+                let (_, addr) = be_u32(value)?;
+                grace.ip_address = Some(Ipv4Addr::from(addr));
+            }
+            _ => {}
+        }
+    }
+    Ok((input, grace))
+}
+
+impl OspfLsaPayload {
+    // Grace LSA is link-scoped, Opaque Area and AS LSAs with opaque type 3
+    // are not Grace LSAs.
+    pub fn as_grace_lsa(&self) -> Option<GraceLsa> {
+        match self {
+            OspfLsaPayload::OpaqueLink(v) if v.opaque_type == OPAQUE_TYPE_GRACE => {
+                parse_grace_lsa(&v.data).ok().map(|(_, grace)| grace)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl OpaqueLsa {
+    pub fn as_te_lsa(&self) -> Option<TeLsa> {
+        if self.opaque_type != OPAQUE_TYPE_TE {
+            return None;
+        }
+        parse_te_lsa(&self.data).ok().map(|(_, te)| te)
+    }
+}
+
+// Traffic Engineering LSA (RFC 3630). It is Opaque Area LSA with opaque type
+// 1 and carries either Router Address TLV or Link TLV.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TeLsa {
+    pub router_address: Option<Ipv4Addr>,
+    pub links: Vec<TeLsaLink>,
+}
+
+// Bandwidth is in bytes per second as IEEE floating point.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TeLsaLink {
+    pub link_type: Option<u8>,
+    pub link_id: Option<Ipv4Addr>,
+    pub local_addrs: Vec<Ipv4Addr>,
+    pub remote_addrs: Vec<Ipv4Addr>,
+    pub te_metric: Option<u32>,
+    pub max_bandwidth: Option<f32>,
+    pub max_resv_bandwidth: Option<f32>,
+    pub unresv_bandwidth: Option<[f32; 8]>,
+    pub admin_group: Option<u32>,
+}
+
+fn parse_te_link(input: &[u8]) -> IResult<&[u8], TeLsaLink> {
+    let (input, tlvs) = many0(parse_tlv)(input)?;
+    let mut link = TeLsaLink::default();
+    for (typ, value) in tlvs {
+        match typ {
+            TE_LINK_TYPE => {
+                let (_, v) = be_u8(value)?;
+                link.link_type = Some(v);
+            }
+            TE_LINK_ID => {
+                let (_, v) = Ipv4Addr::parse_be(value)?;
+                link.link_id = Some(v);
+            }
+            TE_LINK_LOCAL_ADDR => {
+                let (_, v) = many0(Ipv4Addr::parse_be)(value)?;
+                link.local_addrs = v;
+            }
+            TE_LINK_REMOTE_ADDR => {
+                let (_, v) = many0(Ipv4Addr::parse_be)(value)?;
+                link.remote_addrs = v;
+            }
+            TE_LINK_METRIC => {
+                let (_, v) = be_u32(value)?;
+                link.te_metric = Some(v);
+            }
+            TE_LINK_MAX_BANDWIDTH => {
+                let (_, v) = be_f32(value)?;
+                link.max_bandwidth = Some(v);
+            }
+            TE_LINK_MAX_RESV_BANDWIDTH => {
+                let (_, v) = be_f32(value)?;
+                link.max_resv_bandwidth = Some(v);
+            }
+            TE_LINK_UNRESV_BANDWIDTH => {
+                let mut bw = [0f32; 8];
+                let mut value = value;
+                for v in bw.iter_mut() {
+                    let (rem, b) = be_f32(value)?;
+                    *v = b;
+                    value = rem;
+                }
+                link.unresv_bandwidth = Some(bw);
+            }
+            TE_LINK_ADMIN_GROUP => {
+                let (_, v) = be_u32(value)?;
+                link.admin_group = Some(v);
+            }
+            _ => {}
+        }
+    }
+    Ok((input, link))
+}
+
+// Parse TE LSA TLVs in Opaque LSA data. Unknown TLVs are skipped.
+pub fn parse_te_lsa(data: &[u8]) -> IResult<&[u8], TeLsa> {
+    let (input, tlvs) = many0(parse_tlv)(data)?;
+    let mut te = TeLsa::default();
+    for (typ, value) in tlvs {
+        match typ {
+            TE_TLV_ROUTER_ADDRESS => {
+                let (_, addr) = Ipv4Addr::parse_be(value)?;
+                te.router_address = Some(addr);
+            }
+            TE_TLV_LINK => {
+                let (_, link) = parse_te_link(value)?;
+                te.links.push(link);
+            }
+            _ => {}
+        }
+    }
+    Ok((input, te))
+}
@@ -1,22 +1,46 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
 use std::net::Ipv4Addr;
 
 use bitfield_struct::bitfield;
 use byteorder::{BigEndian, ByteOrder};
-use bytes::{BufMut, BytesMut};
+use bytes::{BufMut, Bytes, BytesMut};
 use internet_checksum::Checksum;
 use ipnet::Ipv4Net;
+use nom::bytes::complete::take;
 use nom::error::{make_error, ErrorKind};
-use nom::number::complete::{be_u24, be_u64, be_u8};
+use nom::number::complete::{be_u16, be_u24, be_u32, be_u64, be_u8};
 use nom::{Err, IResult};
 use nom_derive::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use super::util::{many0, Emit, ParseBe};
-use super::{OspfLsType, OspfType};
+use super::{
+    parse_lls, FloodingScope, OspfError, OspfLlsBlock, OspfLsType, OspfType, RouterLsaLinkType,
+    SequenceNumber, OSPF_LSA_HEADER_LEN, OSPF_MIN_PACKET_LEN,
+};
 
 // OSPF version.
 const OSPF_VERSION: u8 = 2;
 
-#[derive(Debug, NomBE)]
+// Maximum age of LSA in seconds.
+pub const LSA_MAX_AGE: u16 = 3600;
+
+// Self-originated LSA is refreshed at this age (LSRefreshTime in RFC 2328
+// Appendix B).
+pub const LSA_REFRESH_TIME: u16 = LSA_MAX_AGE / 2;
+
+// DoNotAge bit of LS age (RFC 4136).
+pub const LSA_DO_NOT_AGE: u16 = 0x8000;
+
+// Maximum age difference for LSA instances to be considered the same.
+const LSA_MAX_AGE_DIFF: u16 = 900;
+
+#[derive(Debug, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ospfv2Packet {
     pub version: u8,
     pub typ: OspfType,
@@ -27,8 +51,13 @@ pub struct Ospfv2Packet {
     pub auth_type: u16,
     #[nom(Parse = "{ |x| Ospfv2Auth::parse_be(x, auth_type) }")]
     pub auth: Ospfv2Auth,
-    #[nom(Parse = "{ |x| Ospfv2Payload::parse_enum(x, typ) }")]
+    #[nom(Parse = "{ |x| Ospfv2Payload::parse_with_length(x, typ, len) }")]
     pub payload: Ospfv2Payload,
+    #[nom(Parse = "{ |x| Ospfv2Auth::parse_digest(x, &auth) }")]
+    pub digest: Vec<u8>,
+    // LLS data block is parsed by parse_validated() when L-bit is set.
+    #[nom(Ignore)]
+    pub lls: Option<OspfLlsBlock>,
 }
 
 impl Ospfv2Packet {
@@ -43,62 +72,203 @@ impl Ospfv2Packet {
             auth_type: 0,
             auth: Ospfv2Auth::default(),
             payload,
+            digest: Vec::new(),
+            lls: None,
+        }
+    }
+
+    pub fn with_password(mut self, pw: &[u8; 8]) -> Self {
+        self.sign_simple_auth(pw);
+        self
+    }
+
+    // Simple password authentication (RFC 2328 Appendix D.3). The password
+    // is carried in clear text in the authentication field.
+    pub fn sign_simple_auth(&mut self, password: &[u8; 8]) {
+        self.auth = Ospfv2Auth::SimplePassword(*password);
+        self.auth_type = self.auth.auth_type();
+    }
+
+    pub fn verify_simple_auth(&self, password: &[u8; 8]) -> bool {
+        match &self.auth {
+            Ospfv2Auth::SimplePassword(pw) => self.auth_type == 1 && pw == password,
+            _ => false,
         }
     }
 
     pub fn emit(&self, buf: &mut BytesMut) {
-        use Ospfv2Payload::*;
+        self.emit_packet(buf);
+        buf.put(&self.digest[..]);
+        if let Some(lls) = &self.lls {
+            lls.emit(buf);
+        }
+    }
+
+    // L-bit in the options of Hello or Database Description.
+    pub fn has_lls(&self) -> bool {
+        match &self.payload {
+            Ospfv2Payload::Hello(v) => v.options.lls_data(),
+            Ospfv2Payload::DbDesc(v) => v.options.lls_data(),
+            _ => false,
+        }
+    }
+
+    // Number of bytes emit() writes, including the message digest and LLS.
+    pub fn byte_len(&self) -> usize {
+        24 + self.payload.byte_len()
+            + self.digest.len()
+            + self.lls.as_ref().map_or(0, |lls| lls.byte_len())
+    }
+
+    pub fn emit_to_bytes(&self) -> Bytes {
+        let mut buf = BytesMut::with_capacity(self.byte_len());
+        self.emit(&mut buf);
+        buf.freeze()
+    }
+
+    pub fn emit_to_vec(&self) -> Vec<u8> {
+        self.emit_to_bytes().to_vec()
+    }
+
+    // Emit the packet with MD5 cryptographic authentication (RFC 2328
+    // Appendix D.4.3). The message digest is appended after the packet.
+    pub fn sign_md5(&mut self, key: &[u8], key_id: u8, seq: u32, buf: &mut BytesMut) {
+        self.auth = Ospfv2Auth::Cryptographic(OspfCryptAuth {
+            key_id,
+            auth_data_len: MD5_DIGEST_LEN as u8,
+            seq,
+        });
+        self.auth_type = self.auth.auth_type();
+        let offset = buf.len();
+        self.emit_packet(buf);
+        self.digest = md5_digest(&buf[offset..], key).to_vec();
+        buf.put(&self.digest[..]);
+    }
+
+    // Verify MD5 message digest of the packet against the key. The input is
+    // the received bytes the packet was parsed from, see verify_md5().
+    pub fn verify_md5(&self, input: &[u8], key: &[u8]) -> bool {
+        matches!(self.auth, Ospfv2Auth::Cryptographic(_)) && verify_md5(input, key)
+    }
+
+    // Emit the packet without the message digest and LLS. Length and checksum
+    // are written relative to the start of the packet in the buffer.
+    fn emit_packet(&self, buf: &mut BytesMut) {
+        let offset = buf.len();
         buf.put_u8(self.version);
-        buf.put_u8(self.typ.into());
+        buf.put_u8(u8::from(self.typ));
         buf.put_u16(self.len);
         buf.put(&self.router_id.octets()[..]);
         buf.put(&self.area_id.octets()[..]);
         buf.put_u16(0);
         buf.put_u16(self.auth_type);
         self.auth.emit(buf);
-        match &self.payload {
-            Hello(v) => v.emit(buf),
-            DbDesc(v) => v.emit(buf),
-            LsRequest(v) => v.emit(buf),
-            LsUpdate(v) => v.emit(buf),
-            LsAck(v) => v.emit(buf),
-            _ => {}
-        }
+        self.payload.emit(buf);
         // OSPF packet length.
-        let len = buf.len() as u16;
-        BigEndian::write_u16(&mut buf[2..4], len);
+        let len = (buf.len() - offset) as u16;
+        BigEndian::write_u16(&mut buf[offset + 2..offset + 4], len);
 
-        // Update checksum.
+        // Checksum is not calculated with cryptographic authentication.
+        if let Ospfv2Auth::Cryptographic(_) = self.auth {
+            return;
+        }
+
+        // Update checksum. Authentication field is excluded.
         const CHECKSUM_RANGE: std::ops::Range<usize> = 12..14;
+        const AUTH_RANGE: std::ops::Range<usize> = 16..24;
+        let packet = &mut buf[offset..];
         let mut cksum = Checksum::new();
-        cksum.add_bytes(buf);
-        buf[CHECKSUM_RANGE].copy_from_slice(&cksum.checksum());
+        cksum.add_bytes(&packet[..AUTH_RANGE.start]);
+        cksum.add_bytes(&packet[AUTH_RANGE.end..]);
+        packet[CHECKSUM_RANGE].copy_from_slice(&cksum.checksum());
     }
 }
 
-#[derive(Debug, Default)]
-pub struct Ospfv2Auth {
-    pub auth: u64,
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Ospfv2Auth {
+    #[default]
+    None,
+    SimplePassword([u8; 8]),
+    Cryptographic(OspfCryptAuth),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OspfCryptAuth {
+    pub key_id: u8,
+    pub auth_data_len: u8,
+    pub seq: u32,
 }
 
 impl Ospfv2Auth {
     pub fn parse_be(input: &[u8], auth_type: u16) -> IResult<&[u8], Self> {
-        if auth_type != 0 {
-            return Err(Err::Error(make_error(input, ErrorKind::Tag)));
+        match auth_type {
+            0 => {
+                let (input, _) = be_u64(input)?;
+                Ok((input, Ospfv2Auth::None))
+            }
+            1 => {
+                let (input, password) = take(8usize)(input)?;
+                let mut pw = [0u8; 8];
+                pw.copy_from_slice(password);
+                Ok((input, Ospfv2Auth::SimplePassword(pw)))
+            }
+            2 => {
+                let (input, _) = be_u16(input)?;
+                let (input, key_id) = be_u8(input)?;
+                let (input, auth_data_len) = be_u8(input)?;
+                let (input, seq) = be_u32(input)?;
+                let auth = OspfCryptAuth {
+                    key_id,
+                    auth_data_len,
+                    seq,
+                };
+                Ok((input, Ospfv2Auth::Cryptographic(auth)))
+            }
+            _ => Err(Err::Error(make_error(input, ErrorKind::Tag))),
+        }
+    }
+
+    pub fn auth_type(&self) -> u16 {
+        match self {
+            Ospfv2Auth::None => 0,
+            Ospfv2Auth::SimplePassword(_) => 1,
+            Ospfv2Auth::Cryptographic(_) => 2,
+        }
+    }
+
+    // Message digest follows the OSPF packet with cryptographic
+    // authentication.
+    pub fn parse_digest<'a>(input: &'a [u8], auth: &Ospfv2Auth) -> IResult<&'a [u8], Vec<u8>> {
+        match auth {
+            Ospfv2Auth::Cryptographic(v) => {
+                let (input, digest) = take(v.auth_data_len as usize)(input)?;
+                Ok((input, digest.to_vec()))
+            }
+            _ => Ok((input, Vec::new())),
         }
-        let (input, auth) = be_u64(input)?;
-        Ok((input, Self { auth }))
     }
 }
 
 impl Emit for Ospfv2Auth {
     fn emit(&self, buf: &mut BytesMut) {
-        buf.put_u64(self.auth);
+        match self {
+            Ospfv2Auth::None => buf.put_u64(0),
+            Ospfv2Auth::SimplePassword(pw) => buf.put(&pw[..]),
+            Ospfv2Auth::Cryptographic(v) => {
+                buf.put_u16(0);
+                buf.put_u8(v.key_id);
+                buf.put_u8(v.auth_data_len);
+                buf.put_u32(v.seq);
+            }
+        }
     }
 }
 
-#[derive(Debug, NomBE)]
+#[derive(Debug, NomBE, Clone, PartialEq, Eq)]
 #[nom(Selector = "OspfType")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Ospfv2Payload {
     #[nom(Selector = "OspfType::Hello")]
     Hello(OspfHello),
@@ -114,6 +284,44 @@ pub enum Ospfv2Payload {
     Unknown(OspfUnknown),
 }
 
+// Unknown payload is not emitted.
+impl Emit for Ospfv2Payload {
+    fn emit(&self, buf: &mut BytesMut) {
+        use Ospfv2Payload::*;
+        let payload: &dyn Emit = match self {
+            Hello(v) => v,
+            DbDesc(v) => v,
+            LsRequest(v) => v,
+            LsUpdate(v) => v,
+            LsAck(v) => v,
+            Unknown(_) => return,
+        };
+        payload.emit(buf);
+    }
+}
+
+// Callbacks invoked by dispatch() for each packet type.
+pub trait OspfPacketHandler {
+    fn handle_hello(&mut self, pkt: &Ospfv2Packet, hello: &OspfHello);
+    fn handle_db_desc(&mut self, pkt: &Ospfv2Packet, desc: &OspfDbDesc);
+    fn handle_ls_request(&mut self, pkt: &Ospfv2Packet, req: &OspfLsRequest);
+    fn handle_ls_update(&mut self, pkt: &Ospfv2Packet, update: &OspfLsUpdate);
+    fn handle_ls_ack(&mut self, pkt: &Ospfv2Packet, ack: &OspfLsAck);
+    fn handle_unknown(&mut self, pkt: &Ospfv2Packet);
+}
+
+pub fn dispatch<H: OspfPacketHandler>(handler: &mut H, pkt: &Ospfv2Packet) {
+    use Ospfv2Payload::*;
+    match &pkt.payload {
+        Hello(v) => handler.handle_hello(pkt, v),
+        DbDesc(v) => handler.handle_db_desc(pkt, v),
+        LsRequest(v) => handler.handle_ls_request(pkt, v),
+        LsUpdate(v) => handler.handle_ls_update(pkt, v),
+        LsAck(v) => handler.handle_ls_ack(pkt, v),
+        Unknown(_) => handler.handle_unknown(pkt),
+    }
+}
+
 // Wrapper to handle unknown.
 impl Ospfv2Payload {
     pub fn parse_enum(input: &[u8], typ: OspfType) -> IResult<&[u8], Ospfv2Payload> {
@@ -123,9 +331,24 @@ impl Ospfv2Payload {
         }
         Ok((input, payload))
     }
+
+    // Payload is bounded by the packet length so that trailing data such as
+    // the message digest is not consumed.
+    pub fn parse_with_length(
+        input: &[u8],
+        typ: OspfType,
+        total_length: u16,
+    ) -> IResult<&[u8], Ospfv2Payload> {
+        // OSPF header is 24 bytes, so payload length is total_length - 24.
+        let payload_length = total_length.saturating_sub(24) as usize;
+        let (input, payload_input) = take(payload_length)(input)?;
+        let (_, payload) = Ospfv2Payload::parse_enum(payload_input, typ)?;
+        Ok((input, payload))
+    }
 }
 
-#[derive(Debug, NomBE)]
+#[derive(Debug, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OspfUnknown {
     #[nom(Ignore)]
     pub typ: OspfType,
@@ -141,7 +364,19 @@ impl Ospfv2Payload {
             LsRequest(_) => OspfType::LsRequest,
             LsUpdate(_) => OspfType::LsUpdate,
             LsAck(_) => OspfType::LsAck,
-            Unknown(_v) => OspfType::Hello,
+            Unknown(v) => v.typ,
+        }
+    }
+
+    pub fn byte_len(&self) -> usize {
+        use Ospfv2Payload::*;
+        match self {
+            Hello(v) => 20 + 4 * v.neighbors.len(),
+            DbDesc(v) => 8 + 20 * v.lsa_headers.len(),
+            LsRequest(v) => 12 * v.reqs.len(),
+            LsUpdate(v) => v.byte_len(),
+            LsAck(v) => 20 * v.lsa_headers.len(),
+            Unknown(_) => 0,
         }
     }
 }
@@ -166,7 +401,8 @@ pub fn parse_router_tos_routes(input: &[u8]) -> IResult<&[u8], Vec<OspfRouterTOS
     many0(OspfRouterTOS::parse_be)(input)
 }
 
-#[derive(Debug, NomBE)]
+#[derive(Debug, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OspfHello {
     pub netmask: Ipv4Addr,
     pub hello_interval: u16,
@@ -181,7 +417,8 @@ pub struct OspfHello {
 }
 
 #[bitfield(u8, debug = true)]
-#[derive(PartialEq)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OspfOptions {
     pub multi_toplogy: bool,
     pub external: bool,
@@ -193,6 +430,63 @@ pub struct OspfOptions {
     pub dn: bool,
 }
 
+// from_bits() and into_bits() are generated by bitfield.
+impl OspfOptions {
+    pub fn to_bits(self) -> u8 {
+        self.into_bits()
+    }
+
+    // Fixed position flags for CLI output, e.g. "-|-|-|LLS|-|-|E|-".
+    pub fn as_str_flags(&self) -> String {
+        [
+            (self.dn(), "DN"),
+            (self.o(), "O"),
+            (self.demand_circuits(), "DC"),
+            (self.lls_data(), "LLS"),
+            (self.nssa(), "N"),
+            (self.multicast(), "MC"),
+            (self.external(), "E"),
+            (self.multi_toplogy(), "MT"),
+        ]
+        .iter()
+        .map(|&(set, name)| if set { name } else { "-" })
+        .collect::<Vec<_>>()
+        .join("|")
+    }
+}
+
+impl std::ops::BitOr for OspfOptions {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self::from_bits(self.into_bits() | rhs.into_bits())
+    }
+}
+
+impl std::ops::BitAnd for OspfOptions {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self::from_bits(self.into_bits() & rhs.into_bits())
+    }
+}
+
+impl std::ops::BitXor for OspfOptions {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        Self::from_bits(self.into_bits() ^ rhs.into_bits())
+    }
+}
+
+impl std::ops::Not for OspfOptions {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self::from_bits(!self.into_bits())
+    }
+}
+
 impl Default for OspfHello {
     fn default() -> Self {
         Self {
@@ -208,8 +502,149 @@ impl Default for OspfHello {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum HelloCompatibilityError {
+    HelloInterval {
+        local: u16,
+        remote: u16,
+    },
+    RouterDeadInterval {
+        local: u32,
+        remote: u32,
+    },
+    Options {
+        local: OspfOptions,
+        remote: OspfOptions,
+    },
+    Netmask {
+        local: Ipv4Addr,
+        remote: Ipv4Addr,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum HelloOptionsError {
+    EBitMismatch,
+    NBitMismatch,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum HelloValidationError {
+    ZeroHelloInterval,
+    DeadIntervalTooSmall,
+    NotMultiple,
+}
+
 impl OspfHello {
-    pub fn emit(&self, buf: &mut BytesMut) {
+    // Hello parameters which must match between neighbors (RFC 2328 section
+    // 10.5).
+    pub fn is_compatible_with(&self, other: &OspfHello) -> bool {
+        self.compatibility_error(other).is_none()
+    }
+
+    // The first mismatched parameter.
+    pub fn compatibility_error(&self, other: &OspfHello) -> Option<HelloCompatibilityError> {
+        use HelloCompatibilityError::*;
+        if self.hello_interval != other.hello_interval {
+            return Some(HelloInterval {
+                local: self.hello_interval,
+                remote: other.hello_interval,
+            });
+        }
+        if self.router_dead_interval != other.router_dead_interval {
+            return Some(RouterDeadInterval {
+                local: self.router_dead_interval,
+                remote: other.router_dead_interval,
+            });
+        }
+        if !self.options_compatible_with(other) {
+            return Some(Options {
+                local: self.options,
+                remote: other.options,
+            });
+        }
+        if self.netmask != other.netmask {
+            return Some(Netmask {
+                local: self.netmask,
+                remote: other.netmask,
+            });
+        }
+        None
+    }
+
+    // Only E-bit and N-bit must match, which represent the area type (RFC
+    // 2328 section 10.5, RFC 3101 section 2.2). The other option bits may
+    // differ between neighbors.
+    pub fn options_error(&self, other: &OspfHello) -> Option<HelloOptionsError> {
+        if self.options.external() != other.options.external() {
+            return Some(HelloOptionsError::EBitMismatch);
+        }
+        if self.options.nssa() != other.options.nssa() {
+            return Some(HelloOptionsError::NBitMismatch);
+        }
+        None
+    }
+
+    pub fn options_compatible_with(&self, other: &OspfHello) -> bool {
+        self.options_error(other).is_none()
+    }
+
+    // RouterDeadInterval is expected to be a multiple of HelloInterval, four
+    // times by default (RFC 2328 Appendix C.3).
+    pub fn validate_intervals(&self) -> Result<(), HelloValidationError> {
+        let hello_interval = self.hello_interval as u32;
+        if hello_interval == 0 {
+            return Err(HelloValidationError::ZeroHelloInterval);
+        }
+        if self.router_dead_interval < hello_interval {
+            return Err(HelloValidationError::DeadIntervalTooSmall);
+        }
+        if self.router_dead_interval % hello_interval != 0 {
+            return Err(HelloValidationError::NotMultiple);
+        }
+        Ok(())
+    }
+
+    // Router Priority 0 means the router is never elected as DR or BDR.
+    pub fn is_dr_eligible(&self) -> bool {
+        self.priority != 0
+    }
+
+    // DR and BDR are identified by the interface address, not the router ID.
+    pub fn is_designated_router(&self, interface_ip: Ipv4Addr) -> bool {
+        self.d_router == interface_ip
+    }
+
+    pub fn is_backup_dr(&self, interface_ip: Ipv4Addr) -> bool {
+        self.bd_router == interface_ip
+    }
+
+    // 0.0.0.0 means no DR is declared.
+    pub fn declared_dr(&self) -> Option<Ipv4Addr> {
+        if self.d_router.is_unspecified() {
+            None
+        } else {
+            Some(self.d_router)
+        }
+    }
+
+    pub fn add_neighbor(&mut self, id: Ipv4Addr) {
+        if !self.has_neighbor(&id) {
+            self.neighbors.push(id);
+        }
+    }
+
+    pub fn remove_neighbor(&mut self, id: &Ipv4Addr) {
+        self.neighbors.retain(|nbr| nbr != id);
+    }
+
+    pub fn has_neighbor(&self, id: &Ipv4Addr) -> bool {
+        self.neighbors.contains(id)
+    }
+}
+
+impl Emit for OspfHello {
+    fn emit(&self, buf: &mut BytesMut) {
         buf.put(&self.netmask.octets()[..]);
         buf.put_u16(self.hello_interval);
         buf.put_u8(self.options.into());
@@ -223,7 +658,8 @@ impl OspfHello {
     }
 }
 
-#[derive(Debug, Default, NomBE, Clone)]
+#[derive(Debug, Default, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OspfDbDesc {
     pub if_mtu: u16,
     #[nom(Map = "|x: u8| x.into()", Parse = "be_u8")]
@@ -235,7 +671,8 @@ pub struct OspfDbDesc {
 }
 
 #[bitfield(u8, debug = true)]
-#[derive(PartialEq)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DbDescFlags {
     pub master: bool,
     pub more: bool,
@@ -249,10 +686,203 @@ impl DbDescFlags {
     pub fn is_all(&self) -> bool {
         self.master() && self.more() && self.init()
     }
+
+    // Flags for each stage of the Database Exchange (RFC 2328 Section 10.8).
+    pub fn initial_master() -> Self {
+        Self::new()
+            .with_init(true)
+            .with_more(true)
+            .with_master(true)
+    }
+
+    pub fn initial_slave() -> Self {
+        Self::new().with_init(true).with_more(true)
+    }
+
+    pub fn continuing_master() -> Self {
+        Self::new().with_more(true).with_master(true)
+    }
+
+    pub fn continuing_slave() -> Self {
+        Self::new().with_more(true)
+    }
+
+    pub fn final_master() -> Self {
+        Self::new().with_master(true)
+    }
+
+    pub fn final_slave() -> Self {
+        Self::new()
+    }
+}
+
+impl DbDescFlags {
+    pub fn to_bits(self) -> u8 {
+        self.into_bits()
+    }
+
+    // Fixed position flags for CLI output, e.g. "I|M|MS|-".
+    pub fn as_str_flags(&self) -> String {
+        [
+            (self.init(), "I"),
+            (self.more(), "M"),
+            (self.master(), "MS"),
+            (self.oob_resync(), "OOB"),
+        ]
+        .iter()
+        .map(|&(set, name)| if set { name } else { "-" })
+        .collect::<Vec<_>>()
+        .join("|")
+    }
+}
+
+impl std::ops::BitOr for DbDescFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self::from_bits(self.into_bits() | rhs.into_bits())
+    }
+}
+
+impl std::ops::BitAnd for DbDescFlags {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self::from_bits(self.into_bits() & rhs.into_bits())
+    }
+}
+
+impl std::ops::BitXor for DbDescFlags {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        Self::from_bits(self.into_bits() ^ rhs.into_bits())
+    }
+}
+
+impl std::ops::Not for DbDescFlags {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self::from_bits(!self.into_bits())
+    }
+}
+
+// Database Description which does not match the state of the exchange. I-bit
+// and M-bit depend on the neighbor state, so only MS-bit is checked here.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DbDescError {
+    WrongMasterSlaveBit,
 }
 
 impl OspfDbDesc {
-    pub fn emit(&self, buf: &mut BytesMut) {
+    // I, M and MS bits are set in the first packet of the master (RFC 2328
+    // Section 10.8).
+    pub fn is_initial_exchange(&self) -> bool {
+        self.flags.is_all()
+    }
+
+    pub fn is_master(&self) -> bool {
+        self.flags.master()
+    }
+
+    pub fn has_more(&self) -> bool {
+        self.flags.more()
+    }
+
+    pub fn is_init(&self) -> bool {
+        self.flags.init()
+    }
+
+    pub fn add_lsa_header(&mut self, header: OspfLsaHeader) {
+        self.lsa_headers.push(header);
+    }
+
+    pub fn with_lsa_header(mut self, header: OspfLsaHeader) -> Self {
+        self.add_lsa_header(header);
+        self
+    }
+
+    // Sent by the master, MS-bit is set.
+    pub fn assert_master_state(&self) -> Result<(), DbDescError> {
+        if !self.flags.master() {
+            return Err(DbDescError::WrongMasterSlaveBit);
+        }
+        Ok(())
+    }
+
+    // Sent by the slave, MS-bit is clear.
+    pub fn assert_slave_state(&self) -> Result<(), DbDescError> {
+        if self.flags.master() {
+            return Err(DbDescError::WrongMasterSlaveBit);
+        }
+        Ok(())
+    }
+
+    // Duplicate is detected by the sequence number and the flags (RFC 2328
+    // Section 10.6).
+    pub fn would_be_retransmit_of(&self, previous: &OspfDbDesc) -> bool {
+        self.seqnum == previous.seqnum && self.flags == previous.flags
+    }
+
+    // The last packet of the exchange carries neither LSA headers nor the M
+    // and I bits.
+    pub fn is_empty_exchange(&self) -> bool {
+        !self.flags.more() && !self.flags.init() && self.lsa_headers.is_empty()
+    }
+
+    // Number of LSA headers which fit in the MTU with the OSPF header (24
+    // bytes) and the fixed fields of Database Description (8 bytes). At least
+    // one header is sent even when the MTU is too small.
+    pub fn max_headers_per_packet(mtu: u16) -> usize {
+        let space = (mtu as usize).saturating_sub(OSPF_MIN_PACKET_LEN + 8);
+        (space / OSPF_LSA_HEADER_LEN).max(1)
+    }
+
+    // Number of packets split_into_pages() returns.
+    pub fn lsa_header_page_count(&self, mtu: u16) -> usize {
+        let per_page = Self::max_headers_per_packet(mtu);
+        self.lsa_headers.len().div_ceil(per_page).max(1)
+    }
+
+    // Split LSA headers into pages which fit in the MTU with the OSPF header
+    // and the fixed fields of Database Description. M-bit is set except the
+    // last page. Sequence numbers are left unchanged.
+    pub fn split_into_pages(&self, mtu: u16) -> Vec<OspfDbDesc> {
+        let per_page = Self::max_headers_per_packet(mtu);
+
+        let mut pages: Vec<OspfDbDesc> = self
+            .lsa_headers
+            .chunks(per_page)
+            .map(|headers| {
+                let mut page = self.clone_without_headers();
+                page.flags.set_more(true);
+                page.lsa_headers = headers.to_vec();
+                page
+            })
+            .collect();
+        if pages.is_empty() {
+            pages.push(self.clone_without_headers());
+        }
+        if let Some(last) = pages.last_mut() {
+            last.flags.set_more(false);
+        }
+        pages
+    }
+
+    fn clone_without_headers(&self) -> OspfDbDesc {
+        OspfDbDesc {
+            if_mtu: self.if_mtu,
+            options: self.options,
+            flags: self.flags,
+            seqnum: self.seqnum,
+            lsa_headers: Vec::new(),
+        }
+    }
+}
+
+impl Emit for OspfDbDesc {
+    fn emit(&self, buf: &mut BytesMut) {
         buf.put_u16(self.if_mtu);
         buf.put_u8(self.options.into());
         buf.put_u8(self.flags.into());
@@ -263,12 +893,14 @@ impl OspfDbDesc {
     }
 }
 
-#[derive(Debug, NomBE)]
+#[derive(Debug, Default, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OspfLsRequest {
     pub reqs: Vec<OspfLsRequestEntry>,
 }
 
-#[derive(Debug, NomBE, PartialOrd, Ord, PartialEq, Eq)]
+#[derive(Debug, NomBE, Clone, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OspfLsRequestEntry {
     pub ls_type: u32,
     pub ls_id: Ipv4Addr,
@@ -276,13 +908,36 @@ pub struct OspfLsRequestEntry {
 }
 
 impl OspfLsRequest {
-    pub fn emit(&self, buf: &mut BytesMut) {
+    // Accepts both owned and borrowed headers.
+    pub fn from_headers<H>(headers: impl IntoIterator<Item = H>) -> Self
+    where
+        H: Borrow<OspfLsaHeader>,
+    {
+        Self {
+            reqs: headers.into_iter().map(|h| h.borrow().into()).collect(),
+        }
+    }
+
+    // Request all LSAs described in the Database Description.
+    pub fn from_db_desc(dd: &OspfDbDesc) -> Self {
+        Self::from_headers(&dd.lsa_headers)
+    }
+}
+
+impl Emit for OspfLsRequest {
+    fn emit(&self, buf: &mut BytesMut) {
         for req in self.reqs.iter() {
             req.emit(buf);
         }
     }
 }
 
+impl From<&OspfLsaHeader> for OspfLsRequestEntry {
+    fn from(h: &OspfLsaHeader) -> Self {
+        Self::new(h.ls_type, h.ls_id, h.adv_router)
+    }
+}
+
 impl OspfLsRequestEntry {
     pub fn new(ls_type: OspfLsType, ls_id: Ipv4Addr, adv_router: Ipv4Addr) -> Self {
         Self {
@@ -292,59 +947,261 @@ impl OspfLsRequestEntry {
         }
     }
 
-    pub fn emit(&self, buf: &mut BytesMut) {
+    // LS type is a 32-bit field in LS Request. A value which does not fit in
+    // 8 bits is reported as Unknown with the low 8 bits.
+    pub fn typed_ls_type(&self) -> OspfLsType {
+        match u8::try_from(self.ls_type) {
+            Ok(v) => OspfLsType::try_from(v).unwrap_or_else(OspfLsType::Unknown),
+            Err(_) => OspfLsType::Unknown(self.ls_type as u8),
+        }
+    }
+
+    pub fn ls_id_addr(&self) -> Ipv4Addr {
+        self.ls_id
+    }
+}
+
+impl Emit for OspfLsRequestEntry {
+    fn emit(&self, buf: &mut BytesMut) {
         buf.put_u32(self.ls_type);
         buf.put(&self.ls_id.octets()[..]);
         buf.put(&self.adv_router.octets()[..]);
     }
 }
 
-#[derive(Debug, NomBE)]
+#[derive(Debug, Default, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OspfLsUpdate {
+    // # of LSAs on the wire. It is used only for parsing, emit() writes the
+    // number of lsas instead.
     pub num_adv: u32,
     #[nom(Count = "num_adv")]
     pub lsas: Vec<OspfLsa>,
 }
 
 impl OspfLsUpdate {
-    pub fn emit(&self, buf: &mut BytesMut) {
-        buf.put_u32(self.num_adv);
-        for lsa in self.lsas.iter() {
-            lsa.emit(buf);
+    // Parse LS Update with LSA length and checksum validation.
+    pub fn parse_strict(input: &[u8]) -> IResult<&[u8], Self> {
+        let (mut input, num_adv) = be_u32(input)?;
+        let mut lsas = Vec::new();
+        for _ in 0..num_adv {
+            let (rem, lsa) = parse_lsa_strict(input)?;
+            lsas.push(lsa);
+            input = rem;
         }
+        Ok((input, Self { num_adv, lsas }))
     }
-}
 
-#[derive(Debug, NomBE)]
-pub struct OspfLsAck {
-    pub lsa_headers: Vec<OspfLsaHeader>,
-}
+    pub fn add_lsa(&mut self, lsa: OspfLsa) {
+        self.lsas.push(lsa);
+        self.num_adv = self.lsas.len() as u32;
+    }
 
-impl Emit for OspfLsAck {
-    fn emit(&self, buf: &mut BytesMut) {
-        for h in self.lsa_headers.iter() {
-            h.emit(buf);
+    pub fn with_lsa(mut self, lsa: OspfLsa) -> Self {
+        self.add_lsa(lsa);
+        self
+    }
+
+    // Acknowledge all LSAs in the update.
+    pub fn into_lsa_ack(self) -> OspfLsAck {
+        OspfLsAck {
+            lsa_headers: self.lsas.into_iter().map(|lsa| lsa.h).collect(),
         }
     }
-}
 
-#[derive(Debug, NomBE, Clone)]
-pub struct OspfLsaHeader {
-    pub ls_age: u16,
-    pub options: u8,
-    pub ls_type: OspfLsType,
-    pub ls_id: Ipv4Addr,
-    pub adv_router: Ipv4Addr,
-    pub ls_seq_number: u32,
-    pub ls_checksum: u16,
-    pub length: u16,
-}
+    pub fn as_lsa_ack(&self) -> OspfLsAck {
+        OspfLsAck::from_lsa_update(self)
+    }
+
+    pub fn byte_len(&self) -> usize {
+        4 + self.lsas.iter().map(|lsa| lsa.byte_len()).sum::<usize>()
+    }
+
+    pub fn num_lsas(&self) -> usize {
+        self.lsas.len()
+    }
+
+    pub fn find_lsa(&self, key: &OspfLsaKey) -> Option<&OspfLsa> {
+        self.lsas.iter().find(|lsa| lsa.h.key() == *key)
+    }
+
+    pub fn find_lsa_header(&self, key: &OspfLsaKey) -> Option<&OspfLsaHeader> {
+        self.find_lsa(key).map(|lsa| &lsa.h)
+    }
+
+    // When the same LSA appears more than once, the latter one is kept.
+    pub fn into_lsa_map(self) -> HashMap<OspfLsaKey, OspfLsa> {
+        self.lsas
+            .into_iter()
+            .map(|lsa| (lsa.h.key(), lsa))
+            .collect()
+    }
+
+    pub fn count_by_type(&self) -> HashMap<OspfLsType, usize> {
+        let mut counts = HashMap::new();
+        for lsa in self.lsas.iter() {
+            *counts.entry(lsa.h.ls_type).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    pub fn contains_type(&self, ls_type: OspfLsType) -> bool {
+        self.lsas.iter().any(|lsa| lsa.h.ls_type == ls_type)
+    }
+
+    pub fn lsas_of_type(&self, ls_type: OspfLsType) -> impl Iterator<Item = &OspfLsa> + '_ {
+        self.lsas.iter().filter(move |lsa| lsa.h.ls_type == ls_type)
+    }
+
+    pub fn filter_by_type(&self, ls_type: OspfLsType) -> OspfLsUpdate {
+        self.lsas_of_type(ls_type)
+            .cloned()
+            .fold(OspfLsUpdate::default(), OspfLsUpdate::with_lsa)
+    }
+
+    // Split into link-local, area and AS scope updates. LSAs of unknown
+    // flooding scope are not included in any of them.
+    pub fn partition_by_scope(&self) -> (OspfLsUpdate, OspfLsUpdate, OspfLsUpdate) {
+        let mut link = OspfLsUpdate::default();
+        let mut area = OspfLsUpdate::default();
+        let mut as_ = OspfLsUpdate::default();
+        for lsa in self.lsas.iter() {
+            match lsa.h.ls_type.flooding_scope() {
+                FloodingScope::LinkLocal => link.add_lsa(lsa.clone()),
+                FloodingScope::Area => area.add_lsa(lsa.clone()),
+                FloodingScope::As => as_.add_lsa(lsa.clone()),
+                FloodingScope::Unknown => {}
+            }
+        }
+        (link, area, as_)
+    }
+
+    // One summary_line() per LSA, each terminated by a newline.
+    pub fn print_lsdb_table(&self) -> String {
+        self.lsas
+            .iter()
+            .map(|lsa| lsa.h.summary_line() + "\n")
+            .collect()
+    }
+}
+
+impl Emit for OspfLsUpdate {
+    fn emit(&self, buf: &mut BytesMut) {
+        buf.put_u32(self.lsas.len() as u32);
+        for lsa in self.lsas.iter() {
+            lsa.emit(buf);
+        }
+    }
+}
+
+#[derive(Debug, Default, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OspfLsAck {
+    pub lsa_headers: Vec<OspfLsaHeader>,
+}
+
+impl OspfLsAck {
+    pub fn from_lsa_update(update: &OspfLsUpdate) -> Self {
+        Self {
+            lsa_headers: update.lsas.iter().map(|lsa| lsa.h.clone()).collect(),
+        }
+    }
+
+    pub fn from_headers(lsa_headers: Vec<OspfLsaHeader>) -> Self {
+        Self { lsa_headers }
+    }
+
+    // Only LS type, LS ID and Advertising Router are compared.
+    pub fn contains(&self, header: &OspfLsaHeader) -> bool {
+        let key = header.key();
+        self.lsa_headers.iter().any(|h| h.key() == key)
+    }
+}
+
+impl Emit for OspfLsAck {
+    fn emit(&self, buf: &mut BytesMut) {
+        for h in self.lsa_headers.iter() {
+            h.emit(buf);
+        }
+    }
+}
+
+// Debug is implemented in disp.rs to show the fields in readable form.
+#[derive(NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OspfLsaHeader {
+    pub ls_age: u16,
+    #[nom(Map = "|x: u8| x.into()", Parse = "be_u8")]
+    pub options: OspfOptions,
+    pub ls_type: OspfLsType,
+    pub ls_id: Ipv4Addr,
+    pub adv_router: Ipv4Addr,
+    pub ls_seq_number: u32,
+    pub ls_checksum: u16,
+    pub length: u16,
+}
+
+// Hash only covers the fields identifying the LSA (LS type, LS ID and
+// Advertising Router), the same fields as OspfLsaKey. This is intentional so
+// that different instances of the same LSA (e.g. different LS age or LS
+// sequence number) have the same hash. Equality still compares all of the
+// fields.
+impl Hash for OspfLsaHeader {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ls_type.hash(state);
+        self.ls_id.hash(state);
+        self.adv_router.hash(state);
+    }
+}
+
+// Ordered by freshness, the more recent instance is greater. LS sequence
+// number, LS checksum and MaxAge are compared as is_newer_than(), then the
+// smaller LS age is more recent. MaxAgeDiff is not applied so that the order
+// is transitive. The remaining fields only break ties to be consistent with
+// equality.
+impl Ord for OspfLsaHeader {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.to_sequence_number()
+            .0
+            .cmp(&other.to_sequence_number().0)
+            .then(self.ls_checksum.cmp(&other.ls_checksum))
+            .then(self.is_maxage().cmp(&other.is_maxage()))
+            .then(other.effective_age().cmp(&self.effective_age()))
+            .then(self.ls_age.cmp(&other.ls_age))
+            .then(u8::from(self.ls_type).cmp(&u8::from(other.ls_type)))
+            .then(self.ls_id.cmp(&other.ls_id))
+            .then(self.adv_router.cmp(&other.adv_router))
+            .then(self.options.to_bits().cmp(&other.options.to_bits()))
+            .then(self.length.cmp(&other.length))
+    }
+}
+
+impl PartialOrd for OspfLsaHeader {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Default for OspfLsaHeader {
+    fn default() -> Self {
+        Self {
+            ls_age: 0,
+            options: OspfOptions::new(),
+            ls_type: OspfLsType::default(),
+            ls_id: Ipv4Addr::UNSPECIFIED,
+            adv_router: Ipv4Addr::UNSPECIFIED,
+            ls_seq_number: SequenceNumber::INITIAL.into(),
+            ls_checksum: 0,
+            length: 20,
+        }
+    }
+}
 
 impl OspfLsaHeader {
     pub fn new(ls_type: OspfLsType, ls_id: Ipv4Addr, adv_router: Ipv4Addr) -> Self {
         Self {
             ls_age: 0,
-            options: 0,
+            options: OspfOptions::new(),
             ls_type,
             ls_id,
             adv_router,
@@ -354,9 +1211,154 @@ impl OspfLsaHeader {
         }
     }
 
+    pub fn key(&self) -> OspfLsaKey {
+        self.into()
+    }
+
+    pub fn to_request_entry(&self) -> OspfLsRequestEntry {
+        self.into()
+    }
+
+    pub fn to_sequence_number(&self) -> SequenceNumber {
+        self.ls_seq_number.into()
+    }
+
+    pub fn from_sequence_number(seq: SequenceNumber) -> u32 {
+        seq.into()
+    }
+
+    // Determine which LSA instance is more recent (RFC 2328 section 13.1).
+    pub fn is_newer_than(&self, other: &OspfLsaHeader) -> bool {
+        let seq = self.to_sequence_number();
+        let other_seq = other.to_sequence_number();
+        if seq != other_seq {
+            return seq.is_more_recent_than(&other_seq);
+        }
+        if self.ls_checksum != other.ls_checksum {
+            return self.ls_checksum > other.ls_checksum;
+        }
+        if self.is_maxage() != other.is_maxage() {
+            return self.is_maxage();
+        }
+        let age = self.effective_age();
+        let other_age = other.effective_age();
+        if age.abs_diff(other_age) > LSA_MAX_AGE_DIFF {
+            return age < other_age;
+        }
+        false
+    }
+
+    // Neither instance is more recent, they are considered to be identical.
+    pub fn same_instance_as(&self, other: &OspfLsaHeader) -> bool {
+        !self.is_newer_than(other) && !other.is_newer_than(self)
+    }
+
+    // Same LSA regardless of the instance, compared by LS type, LS ID and
+    // Advertising Router.
+    pub fn same_lsa_as(&self, other: &OspfLsaHeader) -> bool {
+        self.ls_type == other.ls_type
+            && self.ls_id == other.ls_id
+            && self.adv_router == other.adv_router
+    }
+
+    pub fn is_maxage(&self) -> bool {
+        self.effective_age() == LSA_MAX_AGE
+    }
+
+    pub fn is_do_not_age(&self) -> bool {
+        self.ls_age & LSA_DO_NOT_AGE != 0
+    }
+
+    // LS age without DoNotAge bit.
+    pub fn effective_age(&self) -> u16 {
+        self.ls_age & !LSA_DO_NOT_AGE
+    }
+
+    // One line LSDB entry: type, LS ID, advertising router, sequence number,
+    // checksum and age.
+    pub fn summary_line(&self) -> String {
+        format!(
+            "{:<16} {:<15} {:<15} 0x{:08x}  0x{:04x}  {}",
+            self.ls_type.to_string(),
+            self.ls_id,
+            self.adv_router,
+            self.ls_seq_number,
+            self.ls_checksum,
+            self.effective_age()
+        )
+    }
+
+    // LS age saturates at MaxAge. DoNotAge LSA is not aged.
+    pub fn increment_age(&mut self, delta: u16) {
+        if self.is_do_not_age() {
+            return;
+        }
+        self.ls_age = self.ls_age.saturating_add(delta).min(LSA_MAX_AGE);
+    }
+
+    pub fn most_recent<'a>(a: &'a OspfLsaHeader, b: &'a OspfLsaHeader) -> &'a OspfLsaHeader {
+        if b > a {
+            b
+        } else {
+            a
+        }
+    }
+
+    // E-bit must match, an AS-external-capable router does not accept LSAs
+    // from a stub area router and vice versa.
+    pub fn options_compatible_with(&self, other: &OspfLsaHeader) -> bool {
+        self.options.external() == other.options.external()
+    }
+
+    pub fn is_self_originated(&self, router_id: Ipv4Addr) -> bool {
+        self.adv_router == router_id
+    }
+
+    pub fn requires_refresh(&self, router_id: Ipv4Addr) -> bool {
+        self.is_self_originated(router_id) && self.effective_age() >= LSA_REFRESH_TIME
+    }
+
+    pub fn age_by(&mut self, seconds: u16) {
+        self.increment_age(seconds);
+    }
+
+    pub fn seconds_until_maxage(&self) -> u16 {
+        LSA_MAX_AGE.saturating_sub(self.effective_age())
+    }
+
+    // Premature aging to flush the LSA from the routing domain (RFC 2328
+    // Section 14.1). DoNotAge bit is cleared.
+    pub fn flush(&mut self) {
+        self.ls_age = LSA_MAX_AGE;
+    }
+
+    // Alias of flush(), which is the canonical name.
+    pub fn retire(&mut self) {
+        self.flush();
+    }
+
+    // Validate LS checksum against the LSA bytes (header and payload).
+    pub fn validate_checksum(&self, full_lsa_bytes: &[u8]) -> bool {
+        let len = self.length as usize;
+        if len < 20 || full_lsa_bytes.len() < len {
+            return false;
+        }
+        ospf_lsa_checksum(&full_lsa_bytes[..len]) == self.ls_checksum
+    }
+
+    // Compute LS checksum from the LSA payload bytes. Length field is also
+    // updated since it is covered by the checksum.
+    pub fn compute_and_set_checksum(&mut self, body_bytes: &[u8]) {
+        self.length = (20 + body_bytes.len()) as u16;
+        let mut buf = BytesMut::new();
+        self.emit(&mut buf);
+        buf.put(body_bytes);
+        self.ls_checksum = ospf_lsa_checksum(&buf);
+    }
+
     pub fn emit(&self, buf: &mut BytesMut) {
         buf.put_u16(self.ls_age);
-        buf.put_u8(self.options);
+        buf.put_u8(self.options.into());
         buf.put_u8(self.ls_type.into());
         buf.put(&self.ls_id.octets()[..]);
         buf.put(&self.adv_router.octets()[..]);
@@ -366,21 +1368,170 @@ impl OspfLsaHeader {
     }
 }
 
-#[derive(Debug, NomBE)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OspfLsaKey {
+    pub ls_type: OspfLsType,
+    pub ls_id: u32,
+    pub adv_router: Ipv4Addr,
+}
+
+impl From<&OspfLsaHeader> for OspfLsaKey {
+    fn from(h: &OspfLsaHeader) -> Self {
+        Self {
+            ls_type: h.ls_type,
+            ls_id: h.ls_id.into(),
+            adv_router: h.adv_router,
+        }
+    }
+}
+
+impl From<&OspfLsRequestEntry> for OspfLsaKey {
+    fn from(req: &OspfLsRequestEntry) -> Self {
+        Self {
+            ls_type: req.typed_ls_type(),
+            ls_id: req.ls_id.into(),
+            adv_router: req.adv_router,
+        }
+    }
+}
+
+#[derive(Debug, Default, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OspfLsa {
     pub h: OspfLsaHeader,
-    #[nom(Parse = "{ |x| OspfLsaPayload::parse_lsa_with_length(x, h.ls_type, h.length) }")]
+    #[nom(Parse = "{ |x| OspfLsaPayload::parse_lsa_with_header(x, &h) }")]
     pub lsa: OspfLsaPayload,
 }
 
+impl OspfLsa {
+    // Opaque LSAs of all three flooding scopes go to visit_opaque().
+    pub fn accept<V: OspfLsaVisitor>(&self, visitor: &mut V) {
+        use OspfLsaPayload::*;
+        match &self.lsa {
+            Router(v) => visitor.visit_router(&self.h, v),
+            Network(v) => visitor.visit_network(&self.h, v),
+            Summary(v) => visitor.visit_summary(&self.h, v),
+            SummaryAsbr(v) => visitor.visit_summary_asbr(&self.h, v),
+            AsExternal(v) => visitor.visit_as_external(&self.h, v),
+            NssaAsExternal(v) => visitor.visit_nssa_as_external(&self.h, v),
+            OpaqueLink(v) | OpaqueArea(v) | OpaqueAs(v) => visitor.visit_opaque(&self.h, v),
+            Unknown(v) => visitor.visit_unknown(&self.h, v),
+        }
+    }
+
+    // LS age is 0, length and checksum are filled by emit(). Options has
+    // E-bit set, or N/P-bit for Type-7 LSA.
+    pub fn new(
+        router_id: Ipv4Addr,
+        ls_id: Ipv4Addr,
+        seq: SequenceNumber,
+        payload: OspfLsaPayload,
+    ) -> OspfLsa {
+        use OspfLsaPayload::*;
+        let mut h = OspfLsaHeader::new(payload.ls_type(), ls_id, router_id);
+        h.ls_seq_number = seq.into();
+        h.options = match payload {
+            NssaAsExternal(_) => OspfOptions::new().with_nssa(true),
+            _ => OspfOptions::new().with_external(true),
+        };
+        let mut lsa = payload;
+        if let OpaqueLink(ref mut v) | OpaqueArea(ref mut v) | OpaqueAs(ref mut v) = lsa {
+            v.set_ls_id(ls_id);
+        }
+        OspfLsa { h, lsa }
+    }
+
+    // Router LSA uses the router ID as LS ID.
+    pub fn new_router(
+        router_id: Ipv4Addr,
+        seq: SequenceNumber,
+        links: Vec<RouterLsaLink>,
+    ) -> OspfLsa {
+        let router = RouterLsa {
+            flags: RouterLsaFlags::new(),
+            num_links: links.len() as u16,
+            links,
+        };
+        Self::new(router_id, router_id, seq, OspfLsaPayload::Router(router))
+    }
+
+    pub fn same_lsa_as(&self, other: &OspfLsa) -> bool {
+        self.h.same_lsa_as(&other.h)
+    }
+
+    // Duplicate of the same LSA instance, e.g. received again by flooding.
+    // LS age is not compared since it changes while the LSA is flooded.
+    pub fn is_duplicate_of(&self, other: &OspfLsa) -> bool {
+        self.same_lsa_as(other)
+            && self.h.ls_seq_number == other.h.ls_seq_number
+            && self.h.ls_checksum == other.h.ls_checksum
+            && self.h.options == other.h.options
+            && self.h.length == other.h.length
+    }
+
+    // Flush the LSA, see OspfLsaHeader::flush().
+    pub fn retire(&mut self) {
+        self.h.flush();
+    }
+
+    pub fn is_retired(&self) -> bool {
+        self.h.is_maxage()
+    }
+
+    // P-bit of Type-7 LSA is carried in the N/P bit of the LSA header
+    // options (RFC 3101 section 2.3).
+    pub fn is_propagate(&self) -> bool {
+        match self.lsa {
+            OspfLsaPayload::NssaAsExternal(_) => self.h.options.nssa(),
+            _ => false,
+        }
+    }
+
+    pub fn byte_len(&self) -> usize {
+        20 + self.lsa.byte_len()
+    }
+
+    // Fill length and LS checksum in the header with the values emit()
+    // writes, e.g. before the LSA is installed in the LSDB.
+    pub fn sign(&mut self) {
+        let mut buf = BytesMut::with_capacity(self.byte_len());
+        self.emit(&mut buf);
+        self.h.length = buf.len() as u16;
+        self.h.ls_checksum = BigEndian::read_u16(&buf[16..18]);
+    }
+
+    pub fn emit_to_bytes(&self) -> Bytes {
+        let mut buf = BytesMut::with_capacity(self.byte_len());
+        self.emit(&mut buf);
+        buf.freeze()
+    }
+
+    pub fn emit_to_vec(&self) -> Vec<u8> {
+        self.emit_to_bytes().to_vec()
+    }
+}
+
 impl Emit for OspfLsa {
     fn emit(&self, buf: &mut BytesMut) {
+        let offset = buf.len();
         self.h.emit(buf);
+        self.lsa.emit(buf);
+
+        // LSA length.
+        let len = (buf.len() - offset) as u16;
+        BigEndian::write_u16(&mut buf[offset + 18..offset + 20], len);
+
+        // Update LSA checksum.
+        let (c0, c1) = compute_fletcher_checksum(&buf[offset..]);
+        buf[offset + 16] = c0;
+        buf[offset + 17] = c1;
     }
 }
 
-#[derive(Debug, NomBE)]
+#[derive(Debug, NomBE, Clone, PartialEq, Eq)]
 #[nom(Selector = "OspfLsType")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum OspfLsaPayload {
     #[nom(Selector = "OspfLsType::Router")]
     Router(RouterLsa),
@@ -394,14 +1545,206 @@ pub enum OspfLsaPayload {
     AsExternal(AsExternalLsa),
     #[nom(Selector = "OspfLsType::NssaAsExternal")]
     NssaAsExternal(NssaAsExternalLsa),
-    // OpaqueLink(OpaqueLinkLsa),
-    // OpaqueArea(OpaqueAreaLsa),
-    // OpaqueAs(OpaqueAsLsa),
+    #[nom(Selector = "OspfLsType::OpaqueLink")]
+    OpaqueLink(OpaqueLsa),
+    #[nom(Selector = "OspfLsType::OpaqueArea")]
+    OpaqueArea(OpaqueLsa),
+    #[nom(Selector = "OspfLsType::OpaqueAs")]
+    OpaqueAs(OpaqueLsa),
     #[nom(Selector = "_")]
     Unknown(UnknownLsa),
 }
 
+// Per LSA type callbacks invoked by OspfLsa::accept(). All methods default to
+// no-op so that a visitor only implements the types it is interested in.
+pub trait OspfLsaVisitor {
+    fn visit_router(&mut self, _h: &OspfLsaHeader, _lsa: &RouterLsa) {}
+    fn visit_network(&mut self, _h: &OspfLsaHeader, _lsa: &NetworkLsa) {}
+    fn visit_summary(&mut self, _h: &OspfLsaHeader, _lsa: &SummaryLsa) {}
+    fn visit_summary_asbr(&mut self, _h: &OspfLsaHeader, _lsa: &SummaryLsa) {}
+    fn visit_as_external(&mut self, _h: &OspfLsaHeader, _lsa: &AsExternalLsa) {}
+    fn visit_nssa_as_external(&mut self, _h: &OspfLsaHeader, _lsa: &NssaAsExternalLsa) {}
+    fn visit_opaque(&mut self, _h: &OspfLsaHeader, _lsa: &OpaqueLsa) {}
+    fn visit_unknown(&mut self, _h: &OspfLsaHeader, _lsa: &UnknownLsa) {}
+}
+
+impl Default for OspfLsaPayload {
+    fn default() -> Self {
+        OspfLsaPayload::Router(RouterLsa::default())
+    }
+}
+
+impl From<RouterLsa> for OspfLsaPayload {
+    fn from(lsa: RouterLsa) -> Self {
+        OspfLsaPayload::Router(lsa)
+    }
+}
+
+impl From<NetworkLsa> for OspfLsaPayload {
+    fn from(lsa: NetworkLsa) -> Self {
+        OspfLsaPayload::Network(lsa)
+    }
+}
+
+// Summary LSA for ASBR (Type 4) needs OspfLsaPayload::SummaryAsbr.
+impl From<SummaryLsa> for OspfLsaPayload {
+    fn from(lsa: SummaryLsa) -> Self {
+        OspfLsaPayload::Summary(lsa)
+    }
+}
+
+impl From<AsExternalLsa> for OspfLsaPayload {
+    fn from(lsa: AsExternalLsa) -> Self {
+        OspfLsaPayload::AsExternal(lsa)
+    }
+}
+
 impl OspfLsaPayload {
+    // LS type of unknown LSA is not kept, Unknown(0) is returned.
+    pub fn ls_type(&self) -> OspfLsType {
+        use OspfLsaPayload::*;
+        match self {
+            Router(_) => OspfLsType::Router,
+            Network(_) => OspfLsType::Network,
+            Summary(_) => OspfLsType::Summary,
+            SummaryAsbr(_) => OspfLsType::SummaryAsbr,
+            AsExternal(_) => OspfLsType::AsExternal,
+            NssaAsExternal(_) => OspfLsType::NssaAsExternal,
+            OpaqueLink(_) => OspfLsType::OpaqueLink,
+            OpaqueArea(_) => OspfLsType::OpaqueArea,
+            OpaqueAs(_) => OspfLsType::OpaqueAs,
+            Unknown(_) => OspfLsType::Unknown(0),
+        }
+    }
+
+    pub fn as_router(&self) -> Option<&RouterLsa> {
+        match self {
+            OspfLsaPayload::Router(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_router_mut(&mut self) -> Option<&mut RouterLsa> {
+        match self {
+            OspfLsaPayload::Router(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_network(&self) -> Option<&NetworkLsa> {
+        match self {
+            OspfLsaPayload::Network(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_network_mut(&mut self) -> Option<&mut NetworkLsa> {
+        match self {
+            OspfLsaPayload::Network(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_summary(&self) -> Option<&SummaryLsa> {
+        match self {
+            OspfLsaPayload::Summary(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_summary_mut(&mut self) -> Option<&mut SummaryLsa> {
+        match self {
+            OspfLsaPayload::Summary(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_summary_asbr(&self) -> Option<&SummaryLsa> {
+        match self {
+            OspfLsaPayload::SummaryAsbr(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_summary_asbr_mut(&mut self) -> Option<&mut SummaryLsa> {
+        match self {
+            OspfLsaPayload::SummaryAsbr(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_as_external(&self) -> Option<&AsExternalLsa> {
+        match self {
+            OspfLsaPayload::AsExternal(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_as_external_mut(&mut self) -> Option<&mut AsExternalLsa> {
+        match self {
+            OspfLsaPayload::AsExternal(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_nssa_as_external(&self) -> Option<&NssaAsExternalLsa> {
+        match self {
+            OspfLsaPayload::NssaAsExternal(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_nssa_as_external_mut(&mut self) -> Option<&mut NssaAsExternalLsa> {
+        match self {
+            OspfLsaPayload::NssaAsExternal(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    // Matches Opaque LSA of any flooding scope.
+    pub fn as_opaque(&self) -> Option<&OpaqueLsa> {
+        use OspfLsaPayload::*;
+        match self {
+            OpaqueLink(v) | OpaqueArea(v) | OpaqueAs(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_opaque_mut(&mut self) -> Option<&mut OpaqueLsa> {
+        use OspfLsaPayload::*;
+        match self {
+            OpaqueLink(v) | OpaqueArea(v) | OpaqueAs(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_unknown(&self) -> Option<&UnknownLsa> {
+        match self {
+            OspfLsaPayload::Unknown(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_unknown_mut(&mut self) -> Option<&mut UnknownLsa> {
+        match self {
+            OspfLsaPayload::Unknown(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn byte_len(&self) -> usize {
+        use OspfLsaPayload::*;
+        match self {
+            Router(v) => v.byte_len(),
+            Network(v) => 4 + 4 * v.attached_routers.len(),
+            Summary(v) | SummaryAsbr(v) => 8 + 4 * v.tos_routes.len(),
+            AsExternal(v) => 16 + 12 * v.tos_list.len(),
+            NssaAsExternal(v) => 16 + 12 * v.tos_list.len(),
+            OpaqueLink(v) | OpaqueArea(v) | OpaqueAs(v) => v.data.len(),
+            Unknown(v) => v.data.len(),
+        }
+    }
+
     pub fn parse_lsa(input: &[u8], typ: OspfLsType) -> IResult<&[u8], Self> {
         OspfLsaPayload::parse_be(input, typ)
     }
@@ -411,8 +1754,6 @@ impl OspfLsaPayload {
         typ: OspfLsType,
         total_length: u16,
     ) -> IResult<&[u8], Self> {
-        use nom::bytes::complete::take;
-
         // LSA header is 20 bytes, so payload length is total_length - 20
         let payload_length = total_length.saturating_sub(20) as usize;
 
@@ -433,67 +1774,267 @@ impl OspfLsaPayload {
             }
         }
     }
-}
 
-#[repr(u8)]
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub enum OspfLinkType {
-    P2p = 1,
-    Transit = 2,
-    Stub = 3,
-    Virtual = 4,
+    // Opaque type and Opaque ID are carried in the LS ID field of the LSA
+    // header, fill them in after the payload is parsed.
+    pub fn parse_lsa_with_header<'a>(
+        input: &'a [u8],
+        h: &OspfLsaHeader,
+    ) -> IResult<&'a [u8], Self> {
+        use OspfLsaPayload::*;
+        let (input, mut lsa) = Self::parse_lsa_with_length(input, h.ls_type, h.length)?;
+        if let OpaqueLink(ref mut v) | OpaqueArea(ref mut v) | OpaqueAs(ref mut v) = lsa {
+            v.set_ls_id(h.ls_id);
+        }
+        Ok((input, lsa))
+    }
 }
 
-// #[derive(Debug, PartialEq, Eq, Clone, Copy, NomBE)]
-// pub struct OspfRouterLinkType(pub u8);
+impl Emit for OspfLsaPayload {
+    fn emit(&self, buf: &mut BytesMut) {
+        use OspfLsaPayload::*;
+        match self {
+            Router(v) => v.emit(buf),
+            Network(v) => v.emit(buf),
+            Summary(v) => v.emit(buf),
+            SummaryAsbr(v) => v.emit(buf),
+            AsExternal(v) => v.emit(buf),
+            NssaAsExternal(v) => v.emit(buf),
+            OpaqueLink(v) => v.emit(buf),
+            OpaqueArea(v) => v.emit(buf),
+            OpaqueAs(v) => v.emit(buf),
+            Unknown(v) => v.emit(buf),
+        }
+    }
+}
 
-#[derive(Debug, NomBE)]
+#[derive(Debug, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OspfRouterTOS {
     pub tos: u8,
     pub resved: u8,
     pub metric: u16,
 }
 
-#[derive(Debug, NomBE, Default)]
+impl Emit for OspfRouterTOS {
+    fn emit(&self, buf: &mut BytesMut) {
+        buf.put_u8(self.tos);
+        buf.put_u8(self.resved);
+        buf.put_u16(self.metric);
+    }
+}
+
+#[derive(Debug, NomBE, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RouterLsa {
-    pub flags: u16,
+    #[nom(Map = "|x: u16| x.into()", Parse = "be_u16")]
+    pub flags: RouterLsaFlags,
     pub num_links: u16,
     #[nom(Parse = "parse_router_links")]
     pub links: Vec<RouterLsaLink>,
 }
 
-#[derive(Debug, NomBE)]
+#[bitfield(u16, debug = true)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RouterLsaFlags {
+    #[bits(8)]
+    pub resvd: u8,
+    pub abr: bool,
+    pub asbr: bool,
+    pub virtual_endpoint: bool,
+    pub wildcard_multicast: bool,
+    pub nssa_translator: bool,
+    pub shortcut_abr: bool,
+    #[bits(2)]
+    pub resvd2: u8,
+}
+
+impl RouterLsa {
+    pub fn is_abr(&self) -> bool {
+        self.flags.abr()
+    }
+
+    pub fn is_asbr(&self) -> bool {
+        self.flags.asbr()
+    }
+
+    pub fn is_virtual_endpoint(&self) -> bool {
+        self.flags.virtual_endpoint()
+    }
+
+    // (Link ID, Link Data, metric) of stub links.
+    pub fn stub_networks(&self) -> impl Iterator<Item = (Ipv4Addr, Ipv4Addr, u16)> + '_ {
+        self.links_of(RouterLsaLinkType::Stub)
+    }
+
+    pub fn transit_neighbors(&self) -> impl Iterator<Item = (Ipv4Addr, Ipv4Addr, u16)> + '_ {
+        self.links_of(RouterLsaLinkType::Transit)
+    }
+
+    pub fn point_to_point_neighbors(&self) -> impl Iterator<Item = (Ipv4Addr, Ipv4Addr, u16)> + '_ {
+        self.links_of(RouterLsaLinkType::PointToPoint)
+    }
+
+    fn links_of(
+        &self,
+        link_type: RouterLsaLinkType,
+    ) -> impl Iterator<Item = (Ipv4Addr, Ipv4Addr, u16)> + '_ {
+        self.links
+            .iter()
+            .filter(move |link| link.link_type == link_type)
+            .map(|link| (link.link_id, link.link_data, link.tos_0_metric))
+    }
+
+    pub fn byte_len(&self) -> usize {
+        4 + self
+            .links
+            .iter()
+            .map(|link| 12 + 4 * link.toses.len())
+            .sum::<usize>()
+    }
+}
+
+impl Emit for RouterLsa {
+    fn emit(&self, buf: &mut BytesMut) {
+        buf.put_u16(self.flags.into());
+        buf.put_u16(self.links.len() as u16);
+        for link in self.links.iter() {
+            link.emit(buf);
+        }
+    }
+}
+
+#[derive(Debug, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RouterLsaLink {
     pub link_id: Ipv4Addr,
     pub link_data: Ipv4Addr,
-    pub link_type: u8,
+    pub link_type: RouterLsaLinkType,
     pub num_tos: u8,
     pub tos_0_metric: u16,
-    #[nom(Parse = "parse_router_tos_routes")]
+    #[nom(Count = "num_tos")]
     pub toses: Vec<OspfRouterTOS>,
 }
 
 impl RouterLsaLink {
+    // Stub link to the prefix, same as new_stub().
     pub fn new(prefix: Ipv4Net, metric: u16) -> Self {
+        Self::new_stub(prefix.addr(), prefix.netmask(), metric)
+    }
+
+    fn with_type(
+        link_type: RouterLsaLinkType,
+        link_id: Ipv4Addr,
+        link_data: Ipv4Addr,
+        metric: u16,
+    ) -> Self {
         Self {
-            link_id: prefix.addr(),
-            link_data: prefix.netmask(),
-            link_type: OspfLinkType::Stub as u8,
+            link_id,
+            link_data,
+            link_type,
             num_tos: 0,
             tos_0_metric: metric,
             toses: vec![],
         }
     }
+
+    // Link ID and Link Data for each link type (RFC 2328 Section A.4.2).
+    pub fn new_stub(prefix: Ipv4Addr, mask: Ipv4Addr, metric: u16) -> Self {
+        Self::with_type(RouterLsaLinkType::Stub, prefix, mask, metric)
+    }
+
+    pub fn new_transit(dr_ip: Ipv4Addr, local_ip: Ipv4Addr, metric: u16) -> Self {
+        Self::with_type(RouterLsaLinkType::Transit, dr_ip, local_ip, metric)
+    }
+
+    pub fn new_point_to_point(neighbor_id: Ipv4Addr, local_ip: Ipv4Addr, metric: u16) -> Self {
+        Self::with_type(
+            RouterLsaLinkType::PointToPoint,
+            neighbor_id,
+            local_ip,
+            metric,
+        )
+    }
+
+    pub fn new_virtual(neighbor_id: Ipv4Addr, local_ip: Ipv4Addr, metric: u16) -> Self {
+        Self::with_type(RouterLsaLinkType::Virtual, neighbor_id, local_ip, metric)
+    }
+
+    pub fn is_point_to_point(&self) -> bool {
+        self.link_type == RouterLsaLinkType::PointToPoint
+    }
+
+    pub fn is_transit(&self) -> bool {
+        self.link_type == RouterLsaLinkType::Transit
+    }
+
+    pub fn is_stub(&self) -> bool {
+        self.link_type == RouterLsaLinkType::Stub
+    }
+
+    pub fn is_virtual(&self) -> bool {
+        self.link_type == RouterLsaLinkType::Virtual
+    }
+}
+
+impl Emit for RouterLsaLink {
+    fn emit(&self, buf: &mut BytesMut) {
+        buf.put(&self.link_id.octets()[..]);
+        buf.put(&self.link_data.octets()[..]);
+        buf.put_u8(self.link_type.into());
+        buf.put_u8(self.num_tos);
+        buf.put_u16(self.tos_0_metric);
+        for tos in self.toses.iter() {
+            tos.emit(buf);
+        }
+    }
 }
 
-#[derive(Debug, NomBE)]
+#[derive(Debug, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NetworkLsa {
     pub netmask: Ipv4Addr,
     #[nom(Parse = "parse_ipv4addr_vec")]
     pub attached_routers: Vec<Ipv4Addr>,
 }
 
-#[derive(Debug, NomBE)]
+impl NetworkLsa {
+    pub fn prefix_length(&self) -> u8 {
+        prefix_len(self.netmask)
+    }
+
+    // Network address from the LS ID (DR's interface address).
+    pub fn network_address(&self, dr_ls_id: Ipv4Addr) -> Ipv4Addr {
+        prefix(dr_ls_id, self.netmask).0
+    }
+
+    pub fn to_prefix(&self, dr_ls_id: Ipv4Addr) -> (Ipv4Addr, u8) {
+        prefix(dr_ls_id, self.netmask)
+    }
+}
+
+// Network address and prefix length of the address masked by the netmask.
+fn prefix(addr: Ipv4Addr, netmask: Ipv4Addr) -> (Ipv4Addr, u8) {
+    let network = Ipv4Addr::from(u32::from(addr) & u32::from(netmask));
+    (network, prefix_len(netmask))
+}
+
+fn prefix_len(netmask: Ipv4Addr) -> u8 {
+    u32::from(netmask).leading_ones() as u8
+}
+
+impl Emit for NetworkLsa {
+    fn emit(&self, buf: &mut BytesMut) {
+        buf.put(&self.netmask.octets()[..]);
+        for router in self.attached_routers.iter() {
+            buf.put(&router.octets()[..]);
+        }
+    }
+}
+
+#[derive(Debug, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SummaryLsa {
     pub netmask: Ipv4Addr,
     pub tos: u8,
@@ -503,14 +2044,45 @@ pub struct SummaryLsa {
     pub tos_routes: Vec<TosRoute>,
 }
 
-#[derive(Debug, NomBE)]
+impl SummaryLsa {
+    // Network address is the LS ID masked by the netmask.
+    pub fn to_prefix(&self, ls_id: Ipv4Addr) -> (Ipv4Addr, u8) {
+        prefix(ls_id, self.netmask)
+    }
+
+    pub fn metric_value(&self) -> u32 {
+        self.metric & 0x00ff_ffff
+    }
+}
+
+impl Emit for SummaryLsa {
+    fn emit(&self, buf: &mut BytesMut) {
+        buf.put(&self.netmask.octets()[..]);
+        buf.put_u8(self.tos);
+        buf.put(&self.metric.to_be_bytes()[1..]);
+        for tos in self.tos_routes.iter() {
+            tos.emit(buf);
+        }
+    }
+}
+
+#[derive(Debug, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TosRoute {
     pub tos: u8,
     #[nom(Parse = "be_u24")]
     pub metric: u32,
 }
 
-#[derive(Debug, NomBE)]
+impl Emit for TosRoute {
+    fn emit(&self, buf: &mut BytesMut) {
+        buf.put_u8(self.tos);
+        buf.put(&self.metric.to_be_bytes()[1..]);
+    }
+}
+
+#[derive(Debug, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AsExternalLsa {
     pub netmask: Ipv4Addr,
     pub ext_and_resvd: u8,
@@ -522,10 +2094,51 @@ pub struct AsExternalLsa {
     pub tos_list: Vec<ExternalTosRoute>,
 }
 
-#[derive(Debug, NomBE)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AsExternalMetricType {
+    Type1,
+    Type2,
+}
+
+impl AsExternalLsa {
+    // E-bit is the most significant bit of the ext_and_resvd byte.
+    pub fn is_type2_metric(&self) -> bool {
+        self.ext_and_resvd & 0x80 != 0
+    }
+
+    pub fn metric_type(&self) -> AsExternalMetricType {
+        if self.is_type2_metric() {
+            AsExternalMetricType::Type2
+        } else {
+            AsExternalMetricType::Type1
+        }
+    }
+
+    // 0.0.0.0 means the traffic is forwarded to the advertising router.
+    pub fn forwarding_address_is_set(&self) -> bool {
+        self.forwarding_address != Ipv4Addr::UNSPECIFIED
+    }
+}
+
+impl Emit for AsExternalLsa {
+    fn emit(&self, buf: &mut BytesMut) {
+        buf.put(&self.netmask.octets()[..]);
+        buf.put_u8(self.ext_and_resvd);
+        buf.put(&self.metric.to_be_bytes()[1..]);
+        buf.put(&self.forwarding_address.octets()[..]);
+        buf.put_u32(self.external_route_tag);
+        for tos in self.tos_list.iter() {
+            tos.emit(buf);
+        }
+    }
+}
+
+#[derive(Debug, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NssaAsExternalLsa {
     pub netmask: Ipv4Addr,
-    pub ext_and_tos: u8,
+    pub ext_and_resvd: u8,
     #[nom(Parse = "be_u24")]
     pub metric: u32,
     pub forwarding_address: Ipv4Addr,
@@ -534,7 +2147,36 @@ pub struct NssaAsExternalLsa {
     pub tos_list: Vec<ExternalTosRoute>,
 }
 
-#[derive(Debug, NomBE)]
+impl NssaAsExternalLsa {
+    // E-bit is the most significant bit of the ext_and_resvd byte.
+    pub fn is_type2_metric(&self) -> bool {
+        self.ext_and_resvd & 0x80 != 0
+    }
+
+    pub fn to_prefix(&self, ls_id: Ipv4Addr) -> (Ipv4Addr, u8) {
+        prefix(ls_id, self.netmask)
+    }
+
+    pub fn metric_value(&self) -> u32 {
+        self.metric & 0x00ff_ffff
+    }
+}
+
+impl Emit for NssaAsExternalLsa {
+    fn emit(&self, buf: &mut BytesMut) {
+        buf.put(&self.netmask.octets()[..]);
+        buf.put_u8(self.ext_and_resvd);
+        buf.put(&self.metric.to_be_bytes()[1..]);
+        buf.put(&self.forwarding_address.octets()[..]);
+        buf.put_u32(self.external_route_tag);
+        for tos in self.tos_list.iter() {
+            tos.emit(buf);
+        }
+    }
+}
+
+#[derive(Debug, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ExternalTosRoute {
     pub tos: u8,
     #[nom(Parse = "be_u24")]
@@ -543,17 +2185,90 @@ pub struct ExternalTosRoute {
     pub external_route_tag: u32,
 }
 
-#[derive(Debug, NomBE)]
+impl Emit for ExternalTosRoute {
+    fn emit(&self, buf: &mut BytesMut) {
+        buf.put_u8(self.tos);
+        buf.put(&self.metric.to_be_bytes()[1..]);
+        buf.put_u32(self.forwarding_address);
+        buf.put_u32(self.external_route_tag);
+    }
+}
+
+#[derive(Debug, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OpaqueLsa {
+    #[nom(Ignore)]
+    pub opaque_type: u8,
+    #[nom(Ignore)]
+    pub opaque_id: u32,
+    pub data: Vec<u8>,
+}
+
+impl OpaqueLsa {
+    pub fn new(opaque_type: u8, opaque_id: u32, data: Vec<u8>) -> Self {
+        Self {
+            opaque_type,
+            opaque_id: opaque_id & 0x00ff_ffff,
+            data,
+        }
+    }
+
+    // LS ID is 8 bits Opaque type followed by 24 bits Opaque ID (RFC 5250).
+    pub fn ls_id(&self) -> Ipv4Addr {
+        Ipv4Addr::from(((self.opaque_type as u32) << 24) | (self.opaque_id & 0x00ff_ffff))
+    }
+
+    pub fn set_ls_id(&mut self, ls_id: Ipv4Addr) {
+        let ls_id = u32::from(ls_id);
+        self.opaque_type = (ls_id >> 24) as u8;
+        self.opaque_id = ls_id & 0x00ff_ffff;
+    }
+}
+
+impl Emit for OpaqueLsa {
+    fn emit(&self, buf: &mut BytesMut) {
+        buf.put(&self.data[..]);
+    }
+}
+
+#[derive(Debug, NomBE, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UnknownLsa {
     pub data: Vec<u8>,
 }
 
+impl Emit for UnknownLsa {
+    fn emit(&self, buf: &mut BytesMut) {
+        buf.put(&self.data[..]);
+    }
+}
+
+// Validate OSPF packet checksum. The checksum is the standard IP checksum of
+// the entire packet excluding the 64-bit authentication field, so the auth
+// bytes are skipped (which is same as treating them as zero). Only the bytes
+// covered by the packet length are checked so that trailing data such as the
+// message digest is not included. With cryptographic authentication the
+// checksum is not calculated (RFC 2328 Appendix D.4.3), use verify_md5()
+// instead. A length field shorter than the header or longer than the input is
+// an error.
 pub fn validate_checksum(input: &[u8]) -> IResult<&[u8], ()> {
     const AUTH_RANGE: std::ops::Range<usize> = 16..24;
 
+    if input.len() < AUTH_RANGE.end {
+        return Err(Err::Error(make_error(input, ErrorKind::Eof)));
+    }
+    let len = BigEndian::read_u16(&input[2..4]) as usize;
+    if !(AUTH_RANGE.end..=input.len()).contains(&len) {
+        return Err(Err::Error(make_error(input, ErrorKind::LengthValue)));
+    }
+    let auth_type = BigEndian::read_u16(&input[14..16]);
+    if auth_type == 2 {
+        return Ok((input, ()));
+    }
+
     let mut cksum = Checksum::new();
     cksum.add_bytes(&input[0..AUTH_RANGE.start]);
-    cksum.add_bytes(&input[AUTH_RANGE.end..]);
+    cksum.add_bytes(&input[AUTH_RANGE.end..len]);
     if cksum.checksum() != [0; 2] {
         Err(Err::Error(make_error(input, ErrorKind::Verify)))
     } else {
@@ -561,8 +2276,403 @@ pub fn validate_checksum(input: &[u8]) -> IResult<&[u8], ()> {
     }
 }
 
+// Checksum of the OSPF packet with the checksum field treated as zero. The
+// authentication field is excluded.
+fn packet_checksum(packet: &[u8]) -> u16 {
+    let mut cksum = Checksum::new();
+    cksum.add_bytes(&packet[..12]);
+    cksum.add_bytes(&packet[14..16]);
+    cksum.add_bytes(&packet[24..]);
+    u16::from_be_bytes(cksum.checksum())
+}
+
+// Fletcher checksum of the LSA (RFC 2328 section 12.1.7). LS age is excluded
+// and the checksum field itself is treated as zero.
+pub fn ospf_lsa_checksum(lsa_bytes: &[u8]) -> u16 {
+    const CHECKSUM_RANGE: std::ops::Range<usize> = 16..18;
+
+    if lsa_bytes.len() < 20 {
+        return 0;
+    }
+    let mut c0: i32 = 0;
+    let mut c1: i32 = 0;
+    for (i, &b) in lsa_bytes.iter().enumerate().skip(2) {
+        let b = if CHECKSUM_RANGE.contains(&i) {
+            0
+        } else {
+            b as i32
+        };
+        c0 = (c0 + b) % 255;
+        c1 = (c1 + c0) % 255;
+    }
+    let mut x = ((lsa_bytes.len() - CHECKSUM_RANGE.start - 1) as i32 * c0 - c1) % 255;
+    if x <= 0 {
+        x += 255;
+    }
+    let mut y = 510 - c0 - x;
+    if y > 255 {
+        y -= 255;
+    }
+    ((x as u16) << 8) | y as u16
+}
+
+// Two checksum bytes to be stored at offset 16 and 17 of the LSA. The input
+// is the whole LSA including LS age. Bytes 16 and 17 should be zero; they are
+// treated as zero regardless of the content.
+pub fn compute_fletcher_checksum(lsa_bytes: &[u8]) -> (u8, u8) {
+    let cksum = ospf_lsa_checksum(lsa_bytes);
+    ((cksum >> 8) as u8, cksum as u8)
+}
+
+// Fletcher checksum of the LSA with the stored checksum bytes sums up to zero
+// when the checksum is correct.
+pub fn verify_fletcher_checksum(lsa_bytes: &[u8]) -> bool {
+    if lsa_bytes.len() < 20 {
+        return false;
+    }
+    let mut c0: u32 = 0;
+    let mut c1: u32 = 0;
+    for &b in lsa_bytes.iter().skip(2) {
+        c0 = (c0 + b as u32) % 255;
+        c1 = (c1 + c0) % 255;
+    }
+    c0 == 0 && c1 == 0
+}
+
+const MD5_DIGEST_LEN: usize = 16;
+
+// MD5 digest of the packet followed by the key padded to 16 bytes.
+// Verify MD5 message digest of the received packet against the key (RFC 2328
+// Appendix D.4.3). The digest is calculated over the packet bytes as received,
+// input[..len], and compared with the digest which follows the packet.
+pub fn verify_md5(input: &[u8], key: &[u8]) -> bool {
+    if input.len() < OSPF_MIN_PACKET_LEN {
+        return false;
+    }
+    let auth_type = BigEndian::read_u16(&input[14..16]);
+    let auth_data_len = input[19] as usize;
+    if auth_type != 2 || auth_data_len != MD5_DIGEST_LEN {
+        return false;
+    }
+    let len = BigEndian::read_u16(&input[2..4]) as usize;
+    if len < OSPF_MIN_PACKET_LEN || input.len() < len + auth_data_len {
+        return false;
+    }
+    md5_digest(&input[..len], key)[..] == input[len..len + auth_data_len]
+}
+
+fn md5_digest(packet: &[u8], key: &[u8]) -> [u8; MD5_DIGEST_LEN] {
+    let mut padded = [0u8; MD5_DIGEST_LEN];
+    let len = key.len().min(MD5_DIGEST_LEN);
+    padded[..len].copy_from_slice(&key[..len]);
+
+    let mut ctx = md5::Context::new();
+    ctx.consume(packet);
+    ctx.consume(padded);
+    ctx.compute().0
+}
+
 pub fn parse(input: &[u8]) -> IResult<&[u8], Ospfv2Packet> {
-    // validate_checksum(input)?;
+    if input.len() < OSPF_MIN_PACKET_LEN {
+        return Err(Err::Error(make_error(input, ErrorKind::Eof)));
+    }
     let (input, packet) = Ospfv2Packet::parse_be(input)?;
     Ok((input, packet))
 }
+
+// Parse with the packet length check. The input must be exactly one OSPF
+// packet (and the message digest when cryptographic authentication is used).
+pub fn parse_exact(input: &[u8]) -> IResult<&[u8], Ospfv2Packet> {
+    if input.len() < 4 {
+        return Err(Err::Error(make_error(input, ErrorKind::Eof)));
+    }
+    let len = BigEndian::read_u16(&input[2..4]) as usize;
+    if len < OSPF_MIN_PACKET_LEN {
+        return Err(Err::Error(make_error(input, ErrorKind::LengthValue)));
+    }
+    if input.len() < len {
+        return Err(Err::Error(make_error(input, ErrorKind::Eof)));
+    }
+    let (rem, packet) = Ospfv2Packet::parse_be(input)?;
+    if !rem.is_empty() {
+        return Err(Err::Error(make_error(rem, ErrorKind::LengthValue)));
+    }
+    Ok((rem, packet))
+}
+
+pub fn parse_lsa_header(input: &[u8]) -> IResult<&[u8], OspfLsaHeader> {
+    OspfLsaHeader::parse_be(input)
+}
+
+// Parse LSA without OSPF packet header, e.g. LSA stored in LSDB. The payload
+// is bounded by the length field of the LSA header.
+pub fn parse_lsa(input: &[u8]) -> IResult<&[u8], OspfLsa> {
+    let (rem, h) = parse_lsa_header(input)?;
+    if h.length < 20 {
+        return Err(Err::Error(make_error(input, ErrorKind::LengthValue)));
+    }
+    let (rem, lsa) = OspfLsaPayload::parse_lsa_with_header(rem, &h)?;
+    Ok((rem, OspfLsa { h, lsa }))
+}
+
+// Parse LSA with the length field bounding the payload and LS checksum
+// validation.
+pub fn parse_lsa_strict(input: &[u8]) -> IResult<&[u8], OspfLsa> {
+    let (_, h) = OspfLsaHeader::parse_be(input)?;
+    let len = h.length as usize;
+    if len < 20 {
+        return Err(Err::Error(make_error(input, ErrorKind::LengthValue)));
+    }
+    if input.len() < len {
+        return Err(Err::Error(make_error(input, ErrorKind::Eof)));
+    }
+    if !h.validate_checksum(input) {
+        return Err(Err::Error(make_error(input, ErrorKind::Verify)));
+    }
+    OspfLsa::parse_be(input)
+}
+
+// Lazy version of parsing LSAs in LS Update. The input starts with the first
+// LSA after the # LSAs field. Each LSA is parsed by parse_lsa() on demand and
+// the iteration stops after the first error.
+pub fn parse_ls_update_iter<'a>(
+    input: &'a [u8],
+    num_adv: u32,
+) -> impl Iterator<Item = IResult<&'a [u8], OspfLsa>> {
+    let mut remaining = Some(input);
+    (0..num_adv).map_while(move |_| {
+        let input = remaining.take()?;
+        let res = parse_lsa(input);
+        if let Ok((rem, _)) = res {
+            remaining = Some(rem);
+        }
+        Some(res)
+    })
+}
+
+// Parse with packet checksum validation. LSAs in LS Update are parsed with
+// parse_lsa_strict().
+pub fn parse_strict(input: &[u8]) -> IResult<&[u8], Ospfv2Packet> {
+    validate_checksum(input)?;
+    let (rem, mut packet) = Ospfv2Packet::parse_be(input)?;
+    if let Ospfv2Payload::LsUpdate(_) = packet.payload {
+        let payload = &input[24..(packet.len as usize).max(24)];
+        let (_, update) = OspfLsUpdate::parse_strict(payload)?;
+        packet.payload = Ospfv2Payload::LsUpdate(update);
+    }
+    Ok((rem, packet))
+}
+
+pub fn parse_validated(input: &[u8]) -> IResult<&[u8], Ospfv2Packet> {
+    validate_checksum(input)?;
+    let (input, mut packet) = Ospfv2Packet::parse_be(input)?;
+    if packet.has_lls() {
+        let (input, lls) = parse_lls(input, packet.auth_type)?;
+        packet.lls = Some(lls);
+        return Ok((input, packet));
+    }
+    Ok((input, packet))
+}
+
+// Packet header and packet checksum checks shared by parse_packet() and
+// is_valid(). Returns the packet length.
+fn check_packet(input: &[u8]) -> Result<usize, OspfError> {
+    if input.len() < OSPF_MIN_PACKET_LEN {
+        return Err(OspfError::Truncated);
+    }
+    if input[0] != OSPF_VERSION {
+        return Err(OspfError::UnknownVersion(input[0]));
+    }
+    let len = BigEndian::read_u16(&input[2..4]) as usize;
+    if len < OSPF_MIN_PACKET_LEN || len > input.len() {
+        return Err(OspfError::Length {
+            expected: len,
+            actual: input.len(),
+        });
+    }
+    let auth_type = BigEndian::read_u16(&input[14..16]);
+    if auth_type > 2 {
+        return Err(OspfError::InvalidAuthType(auth_type));
+    }
+    if validate_checksum(input).is_err() {
+        return Err(OspfError::Checksum {
+            expected: packet_checksum(&input[..len]),
+            actual: BigEndian::read_u16(&input[12..14]),
+        });
+    }
+    Ok(len)
+}
+
+// Parse with packet header validation and packet checksum validation. Errors
+// are reported as OspfError instead of nom's error.
+pub fn parse_packet(input: &[u8]) -> Result<Ospfv2Packet, OspfError> {
+    check_packet(input)?;
+    let (_, packet) = Ospfv2Packet::parse_be(input)?;
+    Ok(packet)
+}
+
+// Check the packet header and the packet checksum without parsing the
+// payload. The input must be exactly one OSPF packet (and the message digest
+// when cryptographic authentication is used).
+pub fn is_valid(input: &[u8]) -> Result<(), OspfError> {
+    let len = check_packet(input)?;
+    // Auth Data Len of the cryptographic authentication field.
+    let auth_type = BigEndian::read_u16(&input[14..16]);
+    let digest_len = if auth_type == 2 {
+        input[19] as usize
+    } else {
+        0
+    };
+    if len + digest_len != input.len() {
+        return Err(OspfError::Length {
+            expected: len + digest_len,
+            actual: input.len(),
+        });
+    }
+    Ok(())
+}
+
+pub fn parse_valid(input: &[u8]) -> Result<Ospfv2Packet, OspfError> {
+    is_valid(input)?;
+    let (_, packet) = Ospfv2Packet::parse_be(input)?;
+    Ok(packet)
+}
+
+// Parse from Bytes received from the socket. Parsing itself borrows the
+// buffer and does not copy it; copies happen only when variable-length fields
+// (LSA lists, neighbor lists, opaque data) are collected into owned Vec in
+// the returned packet. The input buffer can be dropped after this returns.
+pub fn parse_bytes(input: Bytes) -> Result<Ospfv2Packet, OspfError> {
+    parse_packet(&input[..])
+}
+
+// Extract a single LSA header at the offset without parsing the rest of the
+// packet. The header is fixed size, so only the 20 bytes are read.
+pub fn parse_lsa_header_from_bytes(
+    input: Bytes,
+    offset: usize,
+) -> Result<OspfLsaHeader, OspfError> {
+    if input.len() < offset.saturating_add(OSPF_LSA_HEADER_LEN) {
+        return Err(OspfError::Truncated);
+    }
+    let (_, h) = OspfLsaHeader::parse_be(&input[offset..offset + OSPF_LSA_HEADER_LEN])?;
+    Ok(h)
+}
+
+// Parse back-to-back OSPF packets, e.g. a raw socket buffer or a pcap
+// payload. Fails on the first packet which does not parse.
+pub fn parse_all(input: &[u8]) -> Result<Vec<Ospfv2Packet>, OspfError> {
+    OspfPacketIter::new(input).collect()
+}
+
+// Lazy version of parse_all(). Each packet is parsed with parse_packet() and
+// the input is advanced by the packet length, the message digest and the LLS
+// data block. The iteration stops after the first error.
+pub struct OspfPacketIter<'a> {
+    remaining: &'a [u8],
+}
+
+impl<'a> OspfPacketIter<'a> {
+    pub fn new(input: &'a [u8]) -> Self {
+        Self { remaining: input }
+    }
+
+    // LLS data block follows the packet and the message digest when L-bit is
+    // set.
+    fn parse_next(&mut self) -> Result<Ospfv2Packet, OspfError> {
+        let mut packet = parse_packet(self.remaining)?;
+        let consumed = packet.len as usize + packet.digest.len();
+        let mut rem = &self.remaining[consumed.min(self.remaining.len())..];
+        if packet.has_lls() {
+            let (input, lls) = parse_lls(rem, packet.auth_type)?;
+            packet.lls = Some(lls);
+            rem = input;
+        }
+        self.remaining = rem;
+        Ok(packet)
+    }
+}
+
+impl Iterator for OspfPacketIter<'_> {
+    type Item = Result<Ospfv2Packet, OspfError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        match self.parse_next() {
+            Ok(packet) => Some(Ok(packet)),
+            Err(err) => {
+                self.remaining = &[];
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+// Borrowed view of an LSA header. Fields are read from the input on access, so
+// scanning LSA headers does not copy them into OspfLsaHeader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OspfLsaHeaderView<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> OspfLsaHeaderView<'a> {
+    pub fn ls_age(&self) -> u16 {
+        BigEndian::read_u16(&self.data[0..2])
+    }
+
+    pub fn options(&self) -> OspfOptions {
+        self.data[2].into()
+    }
+
+    pub fn ls_type(&self) -> OspfLsType {
+        OspfLsType::try_from(self.data[3]).unwrap_or_else(OspfLsType::Unknown)
+    }
+
+    pub fn ls_id(&self) -> Ipv4Addr {
+        Ipv4Addr::from(BigEndian::read_u32(&self.data[4..8]))
+    }
+
+    pub fn adv_router(&self) -> Ipv4Addr {
+        Ipv4Addr::from(BigEndian::read_u32(&self.data[8..12]))
+    }
+
+    pub fn ls_seq_number(&self) -> u32 {
+        BigEndian::read_u32(&self.data[12..16])
+    }
+
+    pub fn ls_checksum(&self) -> u16 {
+        BigEndian::read_u16(&self.data[16..18])
+    }
+
+    pub fn length(&self) -> u16 {
+        BigEndian::read_u16(&self.data[18..20])
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn to_owned_header(self) -> OspfLsaHeader {
+        OspfLsaHeader {
+            ls_age: self.ls_age(),
+            options: self.options(),
+            ls_type: self.ls_type(),
+            ls_id: self.ls_id(),
+            adv_router: self.adv_router(),
+            ls_seq_number: self.ls_seq_number(),
+            ls_checksum: self.ls_checksum(),
+            length: self.length(),
+        }
+    }
+}
+
+pub fn parse_lsa_header_view(input: &[u8]) -> Result<OspfLsaHeaderView<'_>, OspfError> {
+    if input.len() < OSPF_LSA_HEADER_LEN {
+        return Err(OspfError::Truncated);
+    }
+    Ok(OspfLsaHeaderView {
+        data: &input[..OSPF_LSA_HEADER_LEN],
+    })
+}
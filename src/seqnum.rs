@@ -0,0 +1,51 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+// LS sequence number (RFC 2328 section 12.1.6). It is a signed 32-bit integer
+// where 0x80000000 is reserved, 0x80000001 is the initial and 0x7fffffff is
+// the maximum sequence number.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SequenceNumber(pub i32);
+
+impl SequenceNumber {
+    pub const INITIAL: SequenceNumber = SequenceNumber(0x80000001u32 as i32);
+    pub const MAX: SequenceNumber = SequenceNumber(0x7fffffff);
+
+    pub fn increment(&mut self) {
+        if *self == Self::MAX {
+            *self = Self::INITIAL;
+        } else {
+            self.0 += 1;
+        }
+    }
+
+    pub fn is_more_recent_than(&self, other: &SequenceNumber) -> bool {
+        self.0 > other.0
+    }
+}
+
+impl Default for SequenceNumber {
+    fn default() -> Self {
+        Self::INITIAL
+    }
+}
+
+impl Display for SequenceNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{:08x}", self.0 as u32)
+    }
+}
+
+impl From<u32> for SequenceNumber {
+    fn from(val: u32) -> Self {
+        Self(val as i32)
+    }
+}
+
+impl From<SequenceNumber> for u32 {
+    fn from(seq: SequenceNumber) -> Self {
+        seq.0 as u32
+    }
+}
@@ -1,11 +1,15 @@
+use std::convert::TryFrom;
 use std::fmt::Display;
 
 use nom::number::complete::be_u8;
 use nom::IResult;
 use nom_derive::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 #[repr(u8)]
 #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum OspfType {
     #[default]
     Hello = 1,
@@ -22,10 +26,10 @@ impl Display for OspfType {
         let str = match self {
             Hello => "Hello",
             DbDesc => "Database Description",
-            LsRequest => "LS Request",
-            LsUpdate => "LS Update",
-            LsAck => "LS Acknowlegement",
-            Unknown(_) => "Unknown",
+            LsRequest => "Link State Request",
+            LsUpdate => "Link State Update",
+            LsAck => "Link State Acknowledgement",
+            Unknown(v) => return write!(f, "Unknown({})", v),
         };
         write!(f, "{str}")
     }
@@ -33,28 +37,22 @@ impl Display for OspfType {
 
 impl From<OspfType> for u8 {
     fn from(typ: OspfType) -> Self {
-        use OspfType::*;
-        match typ {
-            Hello => 1,
-            DbDesc => 2,
-            LsRequest => 3,
-            LsUpdate => 4,
-            LsAck => 5,
-            Unknown(v) => v,
-        }
+        typ.as_u8()
     }
 }
 
-impl From<u8> for OspfType {
-    fn from(val: u8) -> Self {
+impl TryFrom<u8> for OspfType {
+    type Error = u8;
+
+    fn try_from(typ: u8) -> Result<Self, Self::Error> {
         use OspfType::*;
-        match val {
-            1 => Hello,
-            2 => DbDesc,
-            3 => LsRequest,
-            4 => LsUpdate,
-            5 => LsAck,
-            v => Unknown(v),
+        match typ {
+            1 => Ok(Hello),
+            2 => Ok(DbDesc),
+            3 => Ok(LsRequest),
+            4 => Ok(LsUpdate),
+            5 => Ok(LsAck),
+            v => Err(v),
         }
     }
 }
@@ -62,7 +60,19 @@ impl From<u8> for OspfType {
 impl OspfType {
     pub fn parse_be(input: &[u8]) -> IResult<&[u8], Self> {
         let (input, typ) = be_u8(input)?;
-        let ospf_type: Self = typ.into();
+        let ospf_type = Self::try_from(typ).unwrap_or_else(Self::Unknown);
         Ok((input, ospf_type))
     }
+
+    pub const fn as_u8(self) -> u8 {
+        use OspfType::*;
+        match self {
+            Hello => 1,
+            DbDesc => 2,
+            LsRequest => 3,
+            LsUpdate => 4,
+            LsAck => 5,
+            Unknown(v) => v,
+        }
+    }
 }
@@ -0,0 +1,66 @@
+use std::fmt::{Display, Formatter, Result};
+use std::net::Ipv4Addr;
+
+use super::*;
+
+impl Display for Ospfv3Packet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            r#"== OSPFv3 ==
+ Version: {}
+ Type: {}
+ Length: {}
+ Router ID: {}
+ Area ID: {}
+ Checksum: {:x}
+ Instance ID: {}
+{}"#,
+            self.version,
+            self.typ,
+            self.len,
+            Ipv4Addr::from(self.router_id),
+            Ipv4Addr::from(self.area_id),
+            self.checksum,
+            self.instance_id,
+            self.payload,
+        )
+    }
+}
+
+impl Display for Ospfv3Payload {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        use Ospfv3Payload::*;
+        match self {
+            Hello(v) => write!(f, "{}", v),
+            Unknown(v) => write!(f, "== {} ==\n Length: {}", v.typ, v.payload.len()),
+        }
+    }
+}
+
+impl Display for Ospfv3Hello {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            r#"== Hello ==
+ Interface ID: {}
+ Router priority: {}
+ Options: {:06x}
+ Hello interval: {}
+ Router dead interval: {}
+ DR: {}
+ BDR: {}"#,
+            self.interface_id,
+            self.priority,
+            self.options,
+            self.hello_interval,
+            self.router_dead_interval,
+            self.d_router,
+            self.bd_router,
+        )?;
+        for nei in self.neighbors.iter() {
+            write!(f, "\n Neighbor: {}", nei)?;
+        }
+        Ok(())
+    }
+}
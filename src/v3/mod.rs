@@ -0,0 +1,4 @@
+mod disp;
+mod parser;
+
+pub use parser::*;
@@ -0,0 +1,132 @@
+use std::net::Ipv4Addr;
+
+use bytes::{BufMut, BytesMut};
+use nom::number::complete::be_u24;
+use nom::IResult;
+use nom_derive::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::util::Emit;
+use crate::{parse_ipv4addr_vec, OspfType, OspfUnknown};
+
+// OSPF version.
+const OSPFV3_VERSION: u8 = 3;
+
+#[derive(Debug, NomBE, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ospfv3Packet {
+    pub version: u8,
+    pub typ: OspfType,
+    pub len: u16,
+    pub router_id: u32,
+    pub area_id: u32,
+    pub checksum: u16,
+    pub instance_id: u8,
+    pub reserved: u8,
+    #[nom(Parse = "{ |x| Ospfv3Payload::parse_enum(x, typ) }")]
+    pub payload: Ospfv3Payload,
+}
+
+impl Ospfv3Packet {
+    pub fn new(router_id: u32, area_id: u32, instance_id: u8, payload: Ospfv3Payload) -> Self {
+        Self {
+            version: OSPFV3_VERSION,
+            typ: payload.typ(),
+            len: 0,
+            router_id,
+            area_id,
+            checksum: 0,
+            instance_id,
+            reserved: 0,
+            payload,
+        }
+    }
+
+    // OSPFv3 checksum covers IPv6 pseudo header (RFC 5340 section A.3.1), so
+    // it is not calculated here. Usually it is calculated by the kernel with
+    // IPV6_CHECKSUM socket option.
+    pub fn emit(&self, buf: &mut BytesMut) {
+        use Ospfv3Payload::*;
+        let offset = buf.len();
+        buf.put_u8(self.version);
+        buf.put_u8(u8::from(self.typ));
+        buf.put_u16(self.len);
+        buf.put_u32(self.router_id);
+        buf.put_u32(self.area_id);
+        buf.put_u16(self.checksum);
+        buf.put_u8(self.instance_id);
+        buf.put_u8(self.reserved);
+        match &self.payload {
+            Hello(v) => v.emit(buf),
+            Unknown(v) => buf.put(&v.payload[..]),
+        }
+        // OSPF packet length.
+        let len = (buf.len() - offset) as u16;
+        buf[offset + 2..offset + 4].copy_from_slice(&len.to_be_bytes());
+    }
+}
+
+#[derive(Debug, NomBE, Clone)]
+#[nom(Selector = "OspfType")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Ospfv3Payload {
+    #[nom(Selector = "OspfType::Hello")]
+    Hello(Ospfv3Hello),
+    #[nom(Selector = "_")]
+    Unknown(OspfUnknown),
+}
+
+// Wrapper to handle unknown.
+impl Ospfv3Payload {
+    pub fn parse_enum(input: &[u8], typ: OspfType) -> IResult<&[u8], Ospfv3Payload> {
+        let (input, mut payload) = Ospfv3Payload::parse_be(input, typ)?;
+        if let Ospfv3Payload::Unknown(ref mut v) = payload {
+            v.typ = typ;
+        }
+        Ok((input, payload))
+    }
+
+    pub fn typ(&self) -> OspfType {
+        use Ospfv3Payload::*;
+        match self {
+            Hello(_) => OspfType::Hello,
+            Unknown(v) => v.typ,
+        }
+    }
+}
+
+#[derive(Debug, NomBE, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ospfv3Hello {
+    pub interface_id: u32,
+    pub priority: u8,
+    #[nom(Parse = "be_u24")]
+    pub options: u32,
+    pub hello_interval: u16,
+    pub router_dead_interval: u16,
+    pub d_router: Ipv4Addr,
+    pub bd_router: Ipv4Addr,
+    #[nom(Parse = "parse_ipv4addr_vec")]
+    pub neighbors: Vec<Ipv4Addr>,
+}
+
+impl Emit for Ospfv3Hello {
+    fn emit(&self, buf: &mut BytesMut) {
+        buf.put_u32(self.interface_id);
+        buf.put_u8(self.priority);
+        buf.put(&self.options.to_be_bytes()[1..]);
+        buf.put_u16(self.hello_interval);
+        buf.put_u16(self.router_dead_interval);
+        buf.put(&self.d_router.octets()[..]);
+        buf.put(&self.bd_router.octets()[..]);
+        for nbr in self.neighbors.iter() {
+            buf.put(&nbr.octets()[..]);
+        }
+    }
+}
+
+pub fn parse_v3(input: &[u8]) -> IResult<&[u8], Ospfv3Packet> {
+    let (input, packet) = Ospfv3Packet::parse_be(input)?;
+    Ok((input, packet))
+}
@@ -1,8 +1,71 @@
-use bytes::BytesMut;
+use std::convert::TryFrom;
+use std::net::Ipv4Addr;
+
+use bytes::{Bytes, BytesMut};
 use hex_literal::hex;
 use nom_derive::Parse;
 use ospf_packet::*;
 
+// Hello without neighbors.
+const HELLO: &[u8] = &hex!(
+    "
+    02 01 00 2c c0 a8 aa 08 00 00 00 01 27 3b 00 00
+    00 00 00 00 00 00 00 00 ff ff ff 00 00 0a 02 01
+    00 00 00 28 c0 a8 aa 08 00 00 00 00
+    "
+);
+
+// Database Description with LSA headers.
+const DB_DESC_LSA: &[u8] = &hex!(
+    "
+    02 02 00 ac c0 a8 aa 03 00 00 00 01 f0 67 00 00
+    00 00 00 00 00 00 00 00 05 dc 02 02 41 77 a9 7e
+    00 01 02 01 c0 a8 aa 03 c0 a8 aa 03 80 00 00 01
+    3a 9c 00 30 00 02 02 05 50 d4 10 00 c0 a8 aa 02
+    80 00 00 01 2a 49 00 24 00 02 02 05 94 79 ab 00
+    c0 a8 aa 02 80 00 00 01 34 a5 00 24 00 02 02 05
+    c0 82 78 00 c0 a8 aa 02 80 00 00 01 d3 19 00 24
+    00 02 02 05 c0 a8 00 00 c0 a8 aa 02 80 00 00 01
+    37 08 00 24 00 02 02 05 c0 a8 01 00 c0 a8 aa 02
+    80 00 00 01 2c 12 00 24 00 02 02 05 c0 a8 ac 00
+    c0 a8 aa 02 80 00 00 01 33 41 00 24
+    "
+);
+
+// LS Update with one Router LSA and six AS External LSAs.
+const LS_UPD_MULTI: &[u8] = &hex!(
+    "
+    02 04 01 24 c0 a8 aa 03 00 00 00 01 36 6b 00 00
+    00 00 00 00 00 00 00 00 00 00 00 07 00 02 02 01
+    c0 a8 aa 03 c0 a8 aa 03 80 00 00 01 3a 9c 00 30
+    02 00 00 02 c0 a8 aa 00 ff ff ff 00 03 00 00 0a
+    c0 a8 aa 00 ff ff ff 00 03 00 00 0a 00 03 02 05
+    50 d4 10 00 c0 a8 aa 02 80 00 00 01 2a 49 00 24
+    ff ff ff ff 80 00 00 14 00 00 00 00 00 00 00 00
+    00 03 02 05 94 79 ab 00 c0 a8 aa 02 80 00 00 01
+    34 a5 00 24 ff ff ff 00 80 00 00 14 c0 a8 aa 01
+    00 00 00 00 00 03 02 05 c0 82 78 00 c0 a8 aa 02
+    80 00 00 01 d3 19 00 24 ff ff ff 00 80 00 00 14
+    00 00 00 00 00 00 00 00 00 03 02 05 c0 a8 00 00
+    c0 a8 aa 02 80 00 00 01 37 08 00 24 ff ff ff 00
+    80 00 00 14 00 00 00 00 00 00 00 00 00 03 02 05
+    c0 a8 01 00 c0 a8 aa 02 80 00 00 01 2c 12 00 24
+    ff ff ff 00 80 00 00 14 00 00 00 00 00 00 00 00
+    00 03 02 05 c0 a8 ac 00 c0 a8 aa 02 80 00 00 01
+    33 41 00 24 ff ff ff 00 80 00 00 14 c0 a8 aa 0a
+    00 00 00 00
+    "
+);
+
+// Router LSA with two stub links.
+const ROUTER_LSA: &[u8] = &hex!(
+    "
+    00 02 02 01 c0 a8 aa 03 c0 a8 aa 03 80 00 00 01
+    3a 9c 00 30 02 00 00 02 c0 a8 aa 00 ff ff ff 00
+    03 00 00 0a c0 a8 aa 00 ff ff ff 00 03 00 00 0a
+    "
+);
+
 fn parse_emit(buf: &[u8]) {
     let packet = parse(buf);
     assert!(packet.is_ok());
@@ -24,14 +87,7 @@ fn parse_emit(buf: &[u8]) {
 
 #[test]
 pub fn parse_hello() {
-    const PACKET: &[u8] = &hex!(
-        "
-        02 01 00 2c c0 a8 aa 08 00 00 00 01 27 3b 00 00
-        00 00 00 00 00 00 00 00 ff ff ff 00 00 0a 02 01
-        00 00 00 28 c0 a8 aa 08 00 00 00 00
-        "
-    );
-    parse_emit(PACKET);
+    parse_emit(HELLO);
 }
 
 #[test]
@@ -45,11 +101,12 @@ pub fn parse_unknown() {
     );
     let packet = parse(PACKET);
     println!("{:?}", packet);
-    // assert!(packet.is_ok());
+    assert!(packet.is_ok());
 
-    // let (rem, packet) = packet.unwrap();
-    // assert!(rem.is_empty());
-    // println!("{}", packet);
+    let (rem, packet) = packet.unwrap();
+    assert!(rem.is_empty());
+    assert_eq!(packet.typ, OspfType::Unknown(6));
+    assert_eq!(packet.payload.typ(), OspfType::Unknown(6));
 }
 
 #[test]
@@ -80,22 +137,7 @@ pub fn parse_db_desc() {
 
 #[test]
 pub fn parse_db_desc_lsa() {
-    const PACKET: &[u8] = &hex!(
-        "
-        02 02 00 ac c0 a8 aa 03 00 00 00 01 f0 67 00 00
-        00 00 00 00 00 00 00 00 05 dc 02 02 41 77 a9 7e
-        00 01 02 01 c0 a8 aa 03 c0 a8 aa 03 80 00 00 01
-        3a 9c 00 30 00 02 02 05 50 d4 10 00 c0 a8 aa 02
-        80 00 00 01 2a 49 00 24 00 02 02 05 94 79 ab 00
-        c0 a8 aa 02 80 00 00 01 34 a5 00 24 00 02 02 05
-        c0 82 78 00 c0 a8 aa 02 80 00 00 01 d3 19 00 24
-        00 02 02 05 c0 a8 00 00 c0 a8 aa 02 80 00 00 01
-        37 08 00 24 00 02 02 05 c0 a8 01 00 c0 a8 aa 02
-        80 00 00 01 2c 12 00 24 00 02 02 05 c0 a8 ac 00
-        c0 a8 aa 02 80 00 00 01 33 41 00 24
-        "
-    );
-    parse_emit(PACKET);
+    parse_emit(DB_DESC_LSA);
 }
 
 #[test]
@@ -144,33 +186,27 @@ pub fn parse_ls_upd() {
 
 #[test]
 pub fn parse_ls_upd_multi() {
-    const PACKET: &[u8] = &hex!(
-        "
-        02 04 01 24 c0 a8 aa 03 00 00 00 01 36 6b 00 00
-        00 00 00 00 00 00 00 00 00 00 00 07 00 02 02 01
-        c0 a8 aa 03 c0 a8 aa 03 80 00 00 01 3a 9c 00 30
-        02 00 00 02 c0 a8 aa 00 ff ff ff 00 03 00 00 0a
-        c0 a8 aa 00 ff ff ff 00 03 00 00 0a 00 03 02 05
-        50 d4 10 00 c0 a8 aa 02 80 00 00 01 2a 49 00 24
-        ff ff ff ff 80 00 00 14 00 00 00 00 00 00 00 00
-        00 03 02 05 94 79 ab 00 c0 a8 aa 02 80 00 00 01
-        34 a5 00 24 ff ff ff 00 80 00 00 14 c0 a8 aa 01
-        00 00 00 00 00 03 02 05 c0 82 78 00 c0 a8 aa 02
-        80 00 00 01 d3 19 00 24 ff ff ff 00 80 00 00 14
-        00 00 00 00 00 00 00 00 00 03 02 05 c0 a8 00 00
-        c0 a8 aa 02 80 00 00 01 37 08 00 24 ff ff ff 00
-        80 00 00 14 00 00 00 00 00 00 00 00 00 03 02 05
-        c0 a8 01 00 c0 a8 aa 02 80 00 00 01 2c 12 00 24
-        ff ff ff 00 80 00 00 14 00 00 00 00 00 00 00 00
-        00 03 02 05 c0 a8 ac 00 c0 a8 aa 02 80 00 00 01
-        33 41 00 24 ff ff ff 00 80 00 00 14 c0 a8 aa 0a
-        00 00 00 00
-        "
-    );
-    let (rem, packet) = parse(PACKET).unwrap();
+    let (rem, packet) = parse(LS_UPD_MULTI).unwrap();
     assert_eq!(rem.len(), 0);
     println!("{}", packet);
     println!("rem len: {:?}", rem.len());
+
+    let mut buf = BytesMut::new();
+    packet.emit(&mut buf);
+    assert_eq!(&buf[..], LS_UPD_MULTI);
+
+    let Ospfv2Payload::LsUpdate(ref upd) = packet.payload else {
+        panic!("not LS Update");
+    };
+    let cloned = upd.clone();
+    assert_eq!(cloned.lsas.len(), upd.lsas.len());
+    let lsa = upd.lsas[1].clone();
+    assert_eq!(lsa.h.ls_checksum, upd.lsas[1].h.ls_checksum);
+    assert_eq!(lsa.to_string(), upd.lsas[1].to_string());
+
+    let mut buf = BytesMut::new();
+    packet.clone().emit(&mut buf);
+    assert_eq!(&buf[..], LS_UPD_MULTI);
 }
 
 #[test]
@@ -190,6 +226,10 @@ pub fn parse_ls_upd_router() {
     assert_eq!(rem.len(), 0);
     println!("{}", packet);
     println!("rem len: {:?}", rem.len());
+
+    let mut buf = BytesMut::new();
+    packet.emit(&mut buf);
+    assert_eq!(&buf[..], &PACKET[34..]);
 }
 
 #[test]
@@ -206,6 +246,11 @@ pub fn parse_ls_ack() {
     assert!(rem.is_empty());
     println!("{}", packet);
     println!("rem len: {:?}", rem.len());
+
+    let output = packet.to_string();
+    assert!(output.contains("Link State Acknowledgement"));
+    assert!(output.contains("Advertising router: 192.168.170.3"));
+    assert!(output.contains("LS seq num: 80000002"));
 }
 
 #[test]
@@ -253,3 +298,3100 @@ pub fn parse_unknown2() {
     println!("{:?}", packet);
     println!("rem len: {:?}", rem.len());
 }
+
+#[test]
+pub fn parse_ls_upd_nssa() {
+    const PACKET: &[u8] = &hex!(
+        "
+        02 04 00 40 02 02 02 02 00 00 00 01 f2 56 00 00
+        00 00 00 00 00 00 00 00 00 00 00 01 00 66 28 07
+        ac 10 00 00 02 02 02 02 80 00 00 01 63 ac 00 24
+        ff ff ff fc 80 00 00 64 c0 a8 0a 01 00 00 00 00
+        "
+    );
+    let (rem, packet) = parse(PACKET).unwrap();
+    assert!(rem.is_empty());
+    println!("{}", packet);
+
+    let Ospfv2Payload::LsUpdate(upd) = packet.payload else {
+        panic!("not LS Update");
+    };
+    let lsa = &upd.lsas[0];
+    assert!(lsa.is_propagate());
+    let OspfLsaPayload::NssaAsExternal(ref nssa) = lsa.lsa else {
+        panic!("not NSSA AS External LSA");
+    };
+    assert_eq!(nssa.netmask, Ipv4Addr::new(255, 255, 255, 252));
+    assert!(nssa.is_type2_metric());
+    assert_eq!(nssa.metric, 100);
+    assert_eq!(nssa.forwarding_address, Ipv4Addr::new(192, 168, 10, 1));
+    assert_eq!(nssa.external_route_tag, 0);
+
+    let packet = Ospfv2Packet::new(
+        &packet.router_id,
+        &packet.area_id,
+        Ospfv2Payload::LsUpdate(upd),
+    );
+    let mut buf = BytesMut::new();
+    packet.emit(&mut buf);
+    assert_eq!(&buf[..], PACKET);
+}
+
+#[test]
+pub fn parse_ls_upd_opaque() {
+    const PACKET: &[u8] = &hex!(
+        "
+        02 04 00 38 01 01 01 01 00 00 00 00 19 a2 00 00
+        00 00 00 00 00 00 00 00 00 00 00 01 00 01 22 0a
+        01 00 00 00 01 01 01 01 80 00 00 01 3a ed 00 1c
+        00 01 00 04 01 01 01 01
+        "
+    );
+    let (rem, packet) = parse(PACKET).unwrap();
+    assert!(rem.is_empty());
+    println!("{}", packet);
+
+    let Ospfv2Payload::LsUpdate(upd) = packet.payload else {
+        panic!("not LS Update");
+    };
+    let lsa = &upd.lsas[0];
+    let OspfLsaPayload::OpaqueArea(ref opaque) = lsa.lsa else {
+        panic!("not Opaque Area LSA");
+    };
+    assert_eq!(opaque.opaque_type, 1);
+    assert_eq!(opaque.opaque_id, 0);
+    assert_eq!(opaque.ls_id(), lsa.h.ls_id);
+    assert_eq!(opaque.data, hex!("00 01 00 04 01 01 01 01"));
+
+    let packet = Ospfv2Packet::new(
+        &packet.router_id,
+        &packet.area_id,
+        Ospfv2Payload::LsUpdate(upd),
+    );
+    let mut buf = BytesMut::new();
+    packet.emit(&mut buf);
+    assert_eq!(&buf[..], PACKET);
+}
+
+#[test]
+pub fn lsa_checksum() {
+    const LSA: &[u8] = &hex!(
+        "
+        03 e2 02 01 c0 a8 aa 08 c0 a8 aa 08 80 00 0d c3
+        25 06 00 24 02 00 00 01 c0 a8 aa 00 ff ff ff 00
+        03 00 00 0a
+        "
+    );
+    assert_eq!(ospf_lsa_checksum(LSA), 0x2506);
+
+    let (_, mut h) = OspfLsaHeader::parse_be(LSA).unwrap();
+    assert!(h.validate_checksum(LSA));
+
+    // LS age is not covered by the checksum.
+    let mut lsa = LSA.to_vec();
+    lsa[0] = 0x0e;
+    assert!(h.validate_checksum(&lsa));
+
+    // Any other byte is.
+    lsa[24] = 0xc1;
+    assert!(!h.validate_checksum(&lsa));
+
+    h.ls_checksum = 0;
+    h.compute_and_set_checksum(&LSA[20..]);
+    assert_eq!(h.ls_checksum, 0x2506);
+    assert_eq!(h.length, 0x24);
+}
+
+#[test]
+pub fn emit_router_lsa_links() {
+    let (rem, mut lsa) = OspfLsa::parse_be(ROUTER_LSA).unwrap();
+    assert!(rem.is_empty());
+    let OspfLsaPayload::Router(ref mut router) = lsa.lsa else {
+        panic!("not Router LSA");
+    };
+    assert_eq!(router.num_links, 2);
+    assert_eq!(router.links.len(), 2);
+    assert!(router.links.iter().all(|link| link.toses.is_empty()));
+
+    // Wrong num_links is corrected on emit.
+    router.num_links = 0;
+
+    let update = OspfLsUpdate {
+        num_adv: 1,
+        lsas: vec![lsa],
+    };
+    let packet = Ospfv2Packet::new(
+        &Ipv4Addr::new(192, 168, 170, 3),
+        &Ipv4Addr::new(0, 0, 0, 1),
+        Ospfv2Payload::LsUpdate(update),
+    );
+    let mut buf = BytesMut::new();
+    packet.emit(&mut buf);
+    assert_eq!(&buf[28..], ROUTER_LSA);
+}
+
+#[test]
+pub fn parse_hello_simple_auth() {
+    const PACKET: &[u8] = &hex!(
+        "
+        02 01 00 2c c0 a8 0a 01 00 00 00 00 67 4a 00 01
+        63 69 73 63 6f 00 00 00 ff ff ff 00 00 0a 02 01
+        00 00 00 28 c0 a8 0a 01 00 00 00 00
+        "
+    );
+    let (rem, packet) = parse(PACKET).unwrap();
+    assert!(rem.is_empty());
+    println!("{}", packet);
+    assert_eq!(packet.auth_type, 1);
+    let Ospfv2Auth::SimplePassword(pw) = packet.auth else {
+        panic!("not simple password");
+    };
+    assert_eq!(&pw, b"cisco\0\0\0");
+
+    let Ospfv2Payload::Hello(hello) = packet.payload else {
+        panic!("not Hello");
+    };
+    let packet = Ospfv2Packet::new(
+        &packet.router_id,
+        &packet.area_id,
+        Ospfv2Payload::Hello(hello),
+    )
+    .with_password(b"cisco\0\0\0");
+    let mut buf = BytesMut::new();
+    packet.emit(&mut buf);
+    assert_eq!(&buf[..], PACKET);
+}
+
+#[test]
+pub fn hello_md5_auth() {
+    const PACKET: &[u8] = &hex!(
+        "
+        02 01 00 2c c0 a8 0a 01 00 00 00 00 00 00 00 02
+        00 00 01 10 5f 00 00 01 ff ff ff 00 00 0a 02 01
+        00 00 00 28 c0 a8 0a 01 00 00 00 00 03 89 9e 37
+        8c 4f 85 e2 d9 e5 36 37 de 92 b0 c0
+        "
+    );
+    let (rem, packet) = parse(PACKET).unwrap();
+    assert!(rem.is_empty());
+    println!("{}", packet);
+    let Ospfv2Auth::Cryptographic(ref auth) = packet.auth else {
+        panic!("not cryptographic authentication");
+    };
+    assert_eq!(auth.key_id, 1);
+    assert_eq!(auth.auth_data_len, 16);
+    assert_eq!(auth.seq, 0x5f000001);
+    let Ospfv2Payload::Hello(ref hello) = packet.payload else {
+        panic!("not Hello");
+    };
+    assert!(hello.neighbors.is_empty());
+
+    assert!(verify_md5(PACKET, b"secret"));
+    assert!(!verify_md5(PACKET, b"wrong"));
+    assert!(packet.verify_md5(PACKET, b"secret"));
+    assert!(!packet.verify_md5(PACKET, b"wrong"));
+
+    // Tampered packet.
+    let mut tampered = PACKET.to_vec();
+    tampered[29] = 0x14;
+    assert!(!verify_md5(&tampered, b"secret"));
+    let (_, packet) = parse(&tampered).unwrap();
+
+    // Sign.
+    let Ospfv2Payload::Hello(hello) = packet.payload else {
+        panic!("not Hello");
+    };
+    let mut hello = hello;
+    hello.hello_interval = 10;
+    let mut packet = Ospfv2Packet::new(
+        &Ipv4Addr::new(192, 168, 10, 1),
+        &Ipv4Addr::UNSPECIFIED,
+        Ospfv2Payload::Hello(hello),
+    );
+    let mut buf = BytesMut::new();
+    packet.sign_md5(b"secret", 1, 0x5f000001, &mut buf);
+    assert_eq!(&buf[..], PACKET);
+    assert!(verify_md5(&buf, b"secret"));
+    assert!(packet.verify_md5(&buf, b"secret"));
+
+    // Sign into a buffer which already has data.
+    let mut buf = BytesMut::from(&[0xffu8; 4][..]);
+    packet.sign_md5(b"secret", 1, 0x5f000001, &mut buf);
+    assert_eq!(&buf[..4], &[0xff; 4]);
+    assert_eq!(&buf[4..], PACKET);
+    assert!(verify_md5(&buf[4..], b"secret"));
+}
+
+#[test]
+pub fn verify_md5_unknown_type() {
+    // Unknown payload is not emitted, so the digest must be verified over the
+    // received bytes.
+    const PACKET: &[u8] = &hex!(
+        "
+        02 06 00 2c c0 a8 0a 01 00 00 00 00 00 00 00 02
+        00 00 01 10 5f 00 00 01 ff ff ff 00 00 0a 02 01
+        00 00 00 28 c0 a8 0a 01 00 00 00 00 83 9f f8 ff
+        12 38 87 d0 01 b1 f2 77 4a a6 85 fb
+        "
+    );
+    let (rem, packet) = parse(PACKET).unwrap();
+    assert!(rem.is_empty());
+    assert_eq!(packet.typ, OspfType::Unknown(6));
+    assert!(verify_md5(PACKET, b"secret"));
+    assert!(!verify_md5(PACKET, b"wrong"));
+    assert!(!verify_md5(&PACKET[..40], b"secret"));
+}
+
+#[test]
+pub fn parse_validated_checksum() {
+    assert!(validate_checksum(HELLO).is_ok());
+    assert!(parse_validated(HELLO).is_ok());
+
+    // Authentication field is not covered by the checksum.
+    let mut packet = HELLO.to_vec();
+    packet[16] = 0xff;
+    assert!(parse_validated(&packet).is_ok());
+
+    let mut packet = HELLO.to_vec();
+    packet[29] = 0x0b;
+    assert!(validate_checksum(&packet).is_err());
+    assert!(parse_validated(&packet).is_err());
+    assert!(parse(&packet).is_ok());
+
+    assert!(parse_validated(&HELLO[..16]).is_err());
+}
+
+#[test]
+pub fn validate_checksum_length() {
+    // LS Ack with length 0 and the checksum over the whole input.
+    const PACKET: &[u8] = &hex!(
+        "
+        02 05 00 00 c0 a8 aa 08 00 00 00 01 03 1e 00 00
+        00 00 00 00 00 00 00 00 00 01 02 01 c0 a8 aa 03
+        c0 a8 aa 03 80 00 00 02 38 9d 00 30
+        "
+    );
+    assert!(validate_checksum(PACKET).is_err());
+    assert!(parse_validated(PACKET).is_err());
+
+    // Length longer than the input.
+    let mut packet = PACKET.to_vec();
+    packet[3] = 0x30;
+    assert!(validate_checksum(&packet).is_err());
+}
+
+#[test]
+pub fn parse_exact_length() {
+    assert!(parse_exact(HELLO).is_ok());
+
+    // Under length.
+    assert!(parse_exact(&HELLO[..2]).is_err());
+    assert!(parse_exact(&HELLO[..40]).is_err());
+
+    // Over length.
+    let mut packet = HELLO.to_vec();
+    packet.extend_from_slice(&[0, 0, 0, 0]);
+    assert!(parse_exact(&packet).is_err());
+
+    // Length field smaller than OSPF header.
+    let mut packet = HELLO.to_vec();
+    packet[3] = 0x10;
+    assert!(parse_exact(&packet).is_err());
+}
+
+#[test]
+pub fn parse_strict_lsa() {
+    const PACKET: &[u8] = &hex!(
+        "
+        02 04 00 40 c0 a8 aa 08 00 00 00 01 96 1f 00 00
+        00 00 00 00 00 00 00 00 00 00 00 01 03 e2 02 01
+        c0 a8 aa 08 c0 a8 aa 08 80 00 0d c3 25 06 00 24
+        02 00 00 01 c0 a8 aa 00 ff ff ff 00 03 00 00 0a
+        "
+    );
+    let (rem, _) = parse_strict(PACKET).unwrap();
+    assert!(rem.is_empty());
+    let (rem, lsa) = parse_lsa_strict(&PACKET[28..]).unwrap();
+    assert!(rem.is_empty());
+    assert_eq!(lsa.h.length, 0x24);
+
+    // LS age is not covered by the LS checksum.
+    let mut lsa = PACKET[28..].to_vec();
+    lsa[1] = 0x01;
+    assert!(parse_lsa_strict(&lsa).is_ok());
+
+    // Corrupted LSA.
+    let mut lsa = PACKET[28..].to_vec();
+    lsa[35] = 0x0b;
+    assert!(parse_lsa_strict(&lsa).is_err());
+
+    // LSA length exceeds the input.
+    let mut lsa = PACKET[28..].to_vec();
+    lsa[19] = 0x28;
+    assert!(parse_lsa_strict(&lsa).is_err());
+}
+
+#[test]
+pub fn build_hello() {
+    const PACKET: &[u8] = &hex!(
+        "
+        02 01 00 30 0b 00 00 03 00 00 00 00 d9 91 00 00
+        00 00 00 00 00 00 00 00 ff ff ff 00 00 0a 02 01
+        00 00 00 28 0b 00 00 01 0b 00 00 03 01 01 01 01
+        "
+    );
+    let hello = OspfHello::builder()
+        .netmask(Ipv4Addr::new(255, 255, 255, 0))
+        .hello_interval(10)
+        .options(OspfOptions::new().with_external(true))
+        .priority(1)
+        .router_dead_interval(40)
+        .designated_router(Ipv4Addr::new(11, 0, 0, 1))
+        .backup_designated_router(Ipv4Addr::new(11, 0, 0, 3))
+        .add_neighbor(Ipv4Addr::new(1, 1, 1, 1))
+        .build();
+    let packet = Ospfv2Packet::new(
+        &Ipv4Addr::new(11, 0, 0, 3),
+        &Ipv4Addr::UNSPECIFIED,
+        Ospfv2Payload::Hello(hello),
+    );
+    let mut buf = BytesMut::new();
+    packet.emit(&mut buf);
+    assert_eq!(&buf[..], PACKET);
+}
+
+#[test]
+pub fn build_db_desc() {
+    const PACKET: &[u8] = &hex!(
+        "
+        02 02 00 20 c0 a8 aa 08 00 00 00 01 a0 52 00 00
+        00 00 00 00 00 00 00 00 05 dc 02 07 41 77 a9 7e
+        "
+    );
+    let dd = OspfDbDesc::builder()
+        .mtu(1500)
+        .options(OspfOptions::new().with_external(true))
+        .flags(
+            DbDescFlags::new()
+                .with_master(true)
+                .with_more(true)
+                .with_init(true),
+        )
+        .sequence_number(0x4177a97e)
+        .build();
+    assert!(dd.flags.is_all());
+    let packet = Ospfv2Packet::new(
+        &Ipv4Addr::new(192, 168, 170, 8),
+        &Ipv4Addr::new(0, 0, 0, 1),
+        Ospfv2Payload::DbDesc(dd),
+    );
+    let mut buf = BytesMut::new();
+    packet.emit(&mut buf);
+    assert_eq!(&buf[..], PACKET);
+}
+
+#[test]
+pub fn build_router_lsa() {
+    let prefix = Ipv4Addr::new(192, 168, 170, 0);
+    let mask = Ipv4Addr::new(255, 255, 255, 0);
+    let router = RouterLsa::builder()
+        .flags(RouterLsaFlags::new().with_asbr(true))
+        .add_stub_link(prefix, mask, 10)
+        .add_stub_link(prefix, mask, 10)
+        .build();
+    assert_eq!(router.num_links, 2);
+
+    let router_id = Ipv4Addr::new(192, 168, 170, 3);
+    let mut h = OspfLsaHeader::new(OspfLsType::Router, router_id, router_id);
+    h.ls_age = 2;
+    h.options = OspfOptions::new().with_external(true);
+    h.ls_seq_number = 0x80000001;
+    let lsa = OspfLsa {
+        h,
+        lsa: OspfLsaPayload::Router(router),
+    };
+    let update = OspfLsUpdate {
+        num_adv: 1,
+        lsas: vec![lsa],
+    };
+    let packet = Ospfv2Packet::new(
+        &router_id,
+        &Ipv4Addr::new(0, 0, 0, 1),
+        Ospfv2Payload::LsUpdate(update),
+    );
+    let mut buf = BytesMut::new();
+    packet.emit(&mut buf);
+    assert_eq!(&buf[28..], ROUTER_LSA);
+
+    let router = RouterLsa::builder()
+        .add_transit_link(Ipv4Addr::new(10, 0, 0, 1), Ipv4Addr::new(10, 0, 0, 2), 1)
+        .add_p2p_link(Ipv4Addr::new(2, 2, 2, 2), Ipv4Addr::new(10, 0, 1, 1), 2)
+        .add_virtual_link(Ipv4Addr::new(3, 3, 3, 3), Ipv4Addr::new(10, 0, 2, 1), 3)
+        .build();
+    assert_eq!(router.num_links, 3);
+    assert_eq!(router.links[0].link_type, RouterLsaLinkType::Transit);
+    assert_eq!(router.links[1].link_type, RouterLsaLinkType::PointToPoint);
+    assert_eq!(router.links[2].link_type, RouterLsaLinkType::Virtual);
+}
+
+#[test]
+pub fn router_lsa_link_type() {
+    for v in 0..=u8::MAX {
+        let typ = RouterLsaLinkType::from(v);
+        assert_eq!(u8::from(typ), v);
+    }
+    assert_eq!(RouterLsaLinkType::from(1), RouterLsaLinkType::PointToPoint);
+    assert_eq!(RouterLsaLinkType::from(5), RouterLsaLinkType::Unknown(5));
+    assert_eq!(RouterLsaLinkType::Stub.to_string(), "Stub");
+
+    let link = RouterLsaLink::new("10.0.0.0/24".parse().unwrap(), 10);
+    assert!(link.is_stub());
+    assert!(!link.is_transit());
+    assert!(!link.is_point_to_point());
+    assert!(!link.is_virtual());
+    assert!(link.to_string().contains("Link Type: Stub"));
+}
+
+#[test]
+pub fn router_lsa_flags() {
+    let (_, lsa) = OspfLsa::parse_be(ROUTER_LSA).unwrap();
+    let OspfLsaPayload::Router(ref router) = lsa.lsa else {
+        panic!("not Router LSA");
+    };
+    assert!(router.is_asbr());
+    assert!(!router.is_abr());
+    assert!(!router.is_virtual_endpoint());
+    assert_eq!(u16::from(router.flags), 0x0200);
+    assert!(router.to_string().contains("Flags: ASBR"));
+
+    let flags = RouterLsaFlags::new()
+        .with_abr(true)
+        .with_virtual_endpoint(true);
+    assert_eq!(u16::from(flags), 0x0500);
+    assert_eq!(flags.to_string(), "ABR VL-endpoint");
+}
+
+#[test]
+pub fn lsa_key_map() {
+    use std::collections::HashMap;
+
+    let (_, packet) = parse(LS_UPD_MULTI).unwrap();
+    let Ospfv2Payload::LsUpdate(upd) = packet.payload else {
+        panic!("not LS Update");
+    };
+    let mut lsdb: HashMap<OspfLsaKey, OspfLsa> = HashMap::new();
+    for lsa in upd.lsas {
+        lsdb.insert(lsa.h.key(), lsa);
+    }
+    assert_eq!(lsdb.len(), 7);
+
+    let key = OspfLsaKey {
+        ls_type: OspfLsType::Router,
+        ls_id: u32::from(Ipv4Addr::new(192, 168, 170, 3)),
+        adv_router: Ipv4Addr::new(192, 168, 170, 3),
+    };
+    let lsa = lsdb.get(&key).unwrap();
+    assert_eq!(lsa.h.ls_type, OspfLsType::Router);
+
+    let req = OspfLsRequestEntry::new(
+        OspfLsType::AsExternal,
+        Ipv4Addr::new(192, 168, 172, 0),
+        Ipv4Addr::new(192, 168, 170, 2),
+    );
+    let lsa = lsdb.get(&OspfLsaKey::from(&req)).unwrap();
+    assert_eq!(lsa.h.ls_checksum, 0x3341);
+
+    let key = OspfLsaKey {
+        ls_type: OspfLsType::Network,
+        ls_id: u32::from(Ipv4Addr::new(192, 168, 170, 3)),
+        adv_router: Ipv4Addr::new(192, 168, 170, 3),
+    };
+    assert!(!lsdb.contains_key(&key));
+}
+
+#[test]
+pub fn sequence_number() {
+    let mut seq = SequenceNumber::INITIAL;
+    assert_eq!(seq.to_string(), "0x80000001");
+    seq.increment();
+    assert_eq!(u32::from(seq), 0x80000002);
+    assert!(seq.is_more_recent_than(&SequenceNumber::INITIAL));
+    assert!(!SequenceNumber::INITIAL.is_more_recent_than(&seq));
+
+    // Negative to positive.
+    let mut seq = SequenceNumber::from(0xffffffff);
+    seq.increment();
+    assert_eq!(u32::from(seq), 0);
+    assert!(seq.is_more_recent_than(&SequenceNumber::from(0xffffffff)));
+    assert!(SequenceNumber::MAX.is_more_recent_than(&seq));
+
+    // Wrap around.
+    let mut seq = SequenceNumber::MAX;
+    seq.increment();
+    assert_eq!(seq, SequenceNumber::INITIAL);
+
+    let mut h = OspfLsaHeader::new(
+        OspfLsType::Router,
+        Ipv4Addr::UNSPECIFIED,
+        Ipv4Addr::UNSPECIFIED,
+    );
+    h.ls_seq_number = 0x80000005;
+    let mut seq = h.to_sequence_number();
+    seq.increment();
+    h.ls_seq_number = OspfLsaHeader::from_sequence_number(seq);
+    assert_eq!(h.ls_seq_number, 0x80000006);
+}
+
+#[test]
+pub fn lsa_header_newer() {
+    let id = Ipv4Addr::new(10, 0, 0, 1);
+    let mut a = OspfLsaHeader::new(OspfLsType::Router, id, id);
+    a.ls_seq_number = 0x80000002;
+    a.ls_checksum = 0x1000;
+    a.ls_age = 10;
+    let mut b = a.clone();
+    assert!(a.same_instance_as(&b));
+    assert!(!a.is_newer_than(&b));
+
+    // Sequence number is signed.
+    b.ls_seq_number = 0x00000001;
+    assert!(b.is_newer_than(&a));
+    assert!(!a.is_newer_than(&b));
+    b.ls_seq_number = 0x80000001;
+    assert!(a.is_newer_than(&b));
+
+    // Checksum.
+    b.ls_seq_number = a.ls_seq_number;
+    b.ls_checksum = 0x2000;
+    assert!(b.is_newer_than(&a));
+    assert!(!a.same_instance_as(&b));
+
+    // MaxAge.
+    b.ls_checksum = a.ls_checksum;
+    b.ls_age = 3600;
+    assert!(b.is_newer_than(&a));
+    assert!(!a.is_newer_than(&b));
+    a.ls_age = 3600;
+    assert!(a.same_instance_as(&b));
+
+    // Age difference larger than MaxAgeDiff.
+    a.ls_age = 10;
+    b.ls_age = 911;
+    assert!(a.is_newer_than(&b));
+    b.ls_age = 910;
+    assert!(a.same_instance_as(&b));
+}
+
+#[test]
+pub fn lsa_header_age() {
+    let id = Ipv4Addr::new(10, 0, 0, 1);
+    let mut h = OspfLsaHeader::new(OspfLsType::Router, id, id);
+    h.increment_age(100);
+    assert_eq!(h.effective_age(), 100);
+    assert!(!h.is_maxage());
+    h.increment_age(u16::MAX);
+    assert_eq!(h.ls_age, LSA_MAX_AGE);
+    assert!(h.is_maxage());
+
+    h.ls_age = LSA_DO_NOT_AGE | 100;
+    assert!(h.is_do_not_age());
+    assert_eq!(h.effective_age(), 100);
+    h.increment_age(100);
+    assert_eq!(h.ls_age, LSA_DO_NOT_AGE | 100);
+
+    h.flush();
+    assert!(h.is_maxage());
+    assert!(!h.is_do_not_age());
+}
+
+#[test]
+pub fn ls_request_from_db_desc() {
+    // LS Request for all of the LSAs in the DB Description above.
+    const LS_REQUEST: &[u8] = &hex!(
+        "
+        02 03 00 6c c0 a8 aa 08 00 00 00 01 75 95 00 00
+        00 00 00 00 00 00 00 00 00 00 00 01 c0 a8 aa 03
+        c0 a8 aa 03 00 00 00 05 50 d4 10 00 c0 a8 aa 02
+        00 00 00 05 94 79 ab 00 c0 a8 aa 02 00 00 00 05
+        c0 82 78 00 c0 a8 aa 02 00 00 00 05 c0 a8 00 00
+        c0 a8 aa 02 00 00 00 05 c0 a8 01 00 c0 a8 aa 02
+        00 00 00 05 c0 a8 ac 00 c0 a8 aa 02
+        "
+    );
+    let (_, packet) = parse(DB_DESC_LSA).unwrap();
+    let Ospfv2Payload::DbDesc(dd) = packet.payload else {
+        panic!("not DB Description");
+    };
+    let entry = dd.lsa_headers[0].to_request_entry();
+    assert_eq!(entry.ls_type, 1);
+    assert_eq!(entry.ls_id, Ipv4Addr::new(192, 168, 170, 3));
+    assert_eq!(entry.adv_router, Ipv4Addr::new(192, 168, 170, 3));
+
+    let req = OspfLsRequest::from_headers(&dd.lsa_headers);
+    assert_eq!(req.reqs.len(), dd.lsa_headers.len());
+
+    let packet = Ospfv2Packet::new(
+        &Ipv4Addr::new(192, 168, 170, 8),
+        &Ipv4Addr::new(0, 0, 0, 1),
+        Ospfv2Payload::LsRequest(req),
+    );
+    let mut buf = BytesMut::new();
+    packet.emit(&mut buf);
+    assert_eq!(&buf[..], LS_REQUEST);
+}
+
+#[test]
+pub fn ls_ack_from_ls_update() {
+    const PACKET: &[u8] = &hex!(
+        "
+        02 04 00 40 c0 a8 aa 08 00 00 00 01 96 1f 00 00
+        00 00 00 00 00 00 00 00 00 00 00 01 03 e2 02 01
+        c0 a8 aa 08 c0 a8 aa 08 80 00 0d c3 25 06 00 24
+        02 00 00 01 c0 a8 aa 00 ff ff ff 00 03 00 00 0a
+        "
+    );
+    let (_, packet) = parse(PACKET).unwrap();
+    let Ospfv2Payload::LsUpdate(ref upd) = packet.payload else {
+        panic!("not LS Update");
+    };
+    let ack = OspfLsAck::from_lsa_update(upd);
+    assert_eq!(ack.lsa_headers.len(), 1);
+
+    let packet = Ospfv2Packet::new(
+        &Ipv4Addr::new(192, 168, 170, 3),
+        &packet.area_id,
+        Ospfv2Payload::LsAck(ack),
+    );
+    let mut buf = BytesMut::new();
+    packet.emit(&mut buf);
+    assert_eq!(buf.len(), 44);
+    assert_eq!(&buf[24..], &PACKET[28..48]);
+
+    let (rem, packet) = parse_validated(&buf).unwrap();
+    assert!(rem.is_empty());
+    let Ospfv2Payload::LsAck(ack) = packet.payload else {
+        panic!("not LS Ack");
+    };
+    assert_eq!(ack.lsa_headers[0].ls_checksum, 0x2506);
+
+    let ack = OspfLsAck::from_headers(ack.lsa_headers);
+    assert_eq!(ack.lsa_headers.len(), 1);
+}
+
+#[test]
+pub fn lsa_equality() {
+    let (_, a) = OspfLsa::parse_be(ROUTER_LSA).unwrap();
+    let (_, b) = OspfLsa::parse_be(ROUTER_LSA).unwrap();
+    assert_eq!(a, b);
+    assert_eq!(a.h, b.h);
+    assert_eq!(a.lsa, b.lsa);
+
+    let mut c = b.clone();
+    c.h.ls_age += 1;
+    assert_ne!(a, c);
+    assert_eq!(a.lsa, c.lsa);
+
+    let mut c = b.clone();
+    if let OspfLsaPayload::Router(ref mut router) = c.lsa {
+        router.links[1].tos_0_metric = 20;
+    }
+    assert_eq!(a.h, c.h);
+    assert_ne!(a, c);
+}
+
+#[test]
+pub fn lsa_header_hash() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::hash::{Hash, Hasher};
+
+    fn hash(h: &OspfLsaHeader) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        h.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let id = Ipv4Addr::new(10, 0, 0, 1);
+    let mut a = OspfLsaHeader::new(OspfLsType::Router, id, id);
+    a.ls_seq_number = 0x80000001;
+    let mut b = a.clone();
+    b.ls_seq_number = 0x80000002;
+    b.ls_age = 100;
+    assert_ne!(a, b);
+    assert_eq!(hash(&a), hash(&b));
+
+    let c = OspfLsaHeader::new(OspfLsType::Network, id, id);
+    assert_ne!(hash(&a), hash(&c));
+
+    let mut set = HashSet::new();
+    set.insert(a.clone());
+    set.insert(a.clone());
+    assert_eq!(set.len(), 1);
+    set.insert(c);
+    assert_eq!(set.len(), 2);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+pub fn serde_round_trip() {
+    let (_, packet) = parse(LS_UPD_MULTI).unwrap();
+    let json = serde_json::to_string(&packet).unwrap();
+    let decoded: Ospfv2Packet = serde_json::from_str(&json).unwrap();
+    assert_eq!(packet, decoded);
+
+    let mut buf = BytesMut::new();
+    decoded.emit(&mut buf);
+    assert_eq!(&buf[..], LS_UPD_MULTI);
+}
+
+#[test]
+pub fn parse_packet_error() {
+    let packet = parse_packet(HELLO).unwrap();
+    assert_eq!(packet.typ, OspfType::Hello);
+
+    assert_eq!(parse_packet(&HELLO[..20]), Err(OspfError::Truncated));
+
+    let mut buf = HELLO.to_vec();
+    buf[0] = 3;
+    assert_eq!(parse_packet(&buf), Err(OspfError::UnknownVersion(3)));
+
+    let mut buf = HELLO.to_vec();
+    buf[3] = 0x30;
+    assert_eq!(
+        parse_packet(&buf),
+        Err(OspfError::Length {
+            expected: 48,
+            actual: 44
+        })
+    );
+
+    let mut buf = HELLO.to_vec();
+    buf[15] = 3;
+    assert_eq!(parse_packet(&buf), Err(OspfError::InvalidAuthType(3)));
+
+    let mut buf = HELLO.to_vec();
+    buf[27] = 0x14;
+    assert_eq!(
+        parse_packet(&buf),
+        Err(OspfError::Checksum {
+            expected: 0x2727,
+            actual: 0x273b
+        })
+    );
+
+    let err = nom::error::Error::new(HELLO, nom::error::ErrorKind::Tag);
+    let err = OspfError::from(nom::Err::Error(err));
+    assert!(matches!(err, OspfError::ParseFailed(_)));
+    assert_eq!(err.to_string(), "Parse failed: Tag");
+}
+
+#[test]
+pub fn emit_to_vec() {
+    let (_, packet) = parse(LS_UPD_MULTI).unwrap();
+    assert_eq!(packet.byte_len(), LS_UPD_MULTI.len());
+    let buf = packet.emit_to_vec();
+    assert_eq!(&buf[..], LS_UPD_MULTI);
+    assert_eq!(packet.emit_to_bytes(), LS_UPD_MULTI);
+
+    let (_, decoded) = parse(&buf).unwrap();
+    assert_eq!(packet, decoded);
+
+    let Ospfv2Payload::LsUpdate(ref upd) = packet.payload else {
+        panic!("LS Update expected");
+    };
+    for lsa in upd.lsas.iter() {
+        let buf = lsa.emit_to_vec();
+        assert_eq!(buf.len(), lsa.h.length as usize);
+        assert_eq!(lsa.byte_len(), buf.len());
+        if let OspfLsaPayload::Router(ref router) = lsa.lsa {
+            assert_eq!(router.byte_len(), buf.len() - 20);
+        }
+        let (_, decoded) = OspfLsa::parse_be(&buf).unwrap();
+        assert_eq!(lsa, &decoded);
+    }
+}
+
+#[test]
+pub fn parse_from_bytes() {
+    let input = Bytes::from_static(LS_UPD_MULTI);
+    let packet = parse_bytes(input.clone()).unwrap();
+    let Ospfv2Payload::LsUpdate(ref upd) = packet.payload else {
+        panic!("LS Update expected");
+    };
+
+    // The first LSA header follows OSPF header and # of LSAs.
+    let h = parse_lsa_header_from_bytes(input.clone(), 28).unwrap();
+    assert_eq!(h, upd.lsas[0].h);
+
+    assert_eq!(
+        parse_lsa_header_from_bytes(input.clone(), LS_UPD_MULTI.len() - 19),
+        Err(OspfError::Truncated)
+    );
+    assert_eq!(
+        parse_lsa_header_from_bytes(input, usize::MAX),
+        Err(OspfError::Truncated)
+    );
+}
+
+#[test]
+pub fn display_type_names() {
+    assert_eq!(OspfType::DbDesc.to_string(), "Database Description");
+    assert_eq!(OspfType::LsAck.to_string(), "Link State Acknowledgement");
+    assert_eq!(OspfType::Unknown(9).to_string(), "Unknown(9)");
+    assert_eq!(OspfLsType::SummaryAsbr.to_string(), "Summary (ASBR)");
+    assert_eq!(OspfLsType::NssaAsExternal.to_string(), "NSSA");
+    assert_eq!(OspfLsType::OpaqueAs.to_string(), "Opaque AS");
+    assert_eq!(OspfLsType::Unknown(12).to_string(), "Unknown(12)");
+
+    let id = Ipv4Addr::new(10, 0, 0, 1);
+    let h = OspfLsaHeader::new(OspfLsType::SummaryAsbr, id, id);
+    assert!(h.to_string().contains("LS Type: Summary (ASBR)"));
+
+    let hello = Ospfv2Packet::new(&id, &id, Ospfv2Payload::Hello(OspfHello::default()));
+    assert!(hello.to_string().contains("Type: Hello"));
+}
+
+#[test]
+pub fn display_summary_lsa() {
+    const LSA: &[u8] = &hex!(
+        "
+        00 0b 22 03 c0 a8 0a 00 04 04 04 04 80 00 00 01
+        1e 7d 00 1c ff ff ff 00 00 00 00 1e
+        "
+    );
+    let (_, lsa) = OspfLsa::parse_be(LSA).unwrap();
+    let output = lsa.to_string();
+    assert!(output.contains("== Summary LSA =="));
+    assert!(output.contains("Netmask: 255.255.255.0"));
+    assert!(output.contains("Metric: 30"));
+
+    let mut lsa = lsa;
+    lsa.h.ls_type = OspfLsType::SummaryAsbr;
+    let OspfLsaPayload::Summary(summary) = lsa.lsa else {
+        panic!("Summary LSA expected");
+    };
+    let mut summary = summary;
+    summary.tos_routes.push(TosRoute {
+        tos: 8,
+        metric: 100,
+    });
+    lsa.lsa = OspfLsaPayload::SummaryAsbr(summary);
+    let output = lsa.to_string();
+    assert!(output.contains("Metric: 30"));
+    assert!(output.contains("ToS: 8 Metric: 100"));
+}
+
+#[test]
+pub fn display_unknown_lsa() {
+    let lsa = UnknownLsa {
+        data: b"Hello OSPF world\x00\x01\x02\xff".to_vec(),
+    };
+    assert_eq!(
+        lsa.to_string(),
+        "== Unknown LSA ==\n  Length: 20\n  \
+         00000000  48 65 6c 6c 6f 20 4f 53  50 46 20 77 6f 72 6c 64  |Hello OSPF world|\n  \
+         00000010  00 01 02 ff                                       |....|"
+    );
+
+    let lsa = UnknownLsa { data: vec![] };
+    assert_eq!(lsa.to_string(), "== Unknown LSA ==\n  Length: 0");
+}
+
+#[test]
+pub fn lsa_header_options() {
+    let (_, packet) = parse(LS_UPD_MULTI).unwrap();
+    let Ospfv2Payload::LsUpdate(ref upd) = packet.payload else {
+        panic!("LS Update expected");
+    };
+    let h = &upd.lsas[0].h;
+    assert!(h.options.external());
+    assert!(!h.options.nssa());
+    assert_eq!(u8::from(h.options), 0x02);
+    assert!(h.to_string().contains("Options: E\n"));
+
+    assert_eq!(packet.emit_to_vec(), LS_UPD_MULTI);
+}
+
+#[test]
+pub fn multicast_dest() {
+    assert_eq!(OSPF_IP_PROTOCOL, 89);
+    assert_eq!(ALL_SPF_ROUTERS, Ipv4Addr::new(224, 0, 0, 5));
+    assert_eq!(ALL_D_ROUTERS, Ipv4Addr::new(224, 0, 0, 6));
+
+    let id = Ipv4Addr::new(10, 0, 0, 1);
+    let hello = Ospfv2Packet::new(&id, &id, Ospfv2Payload::Hello(OspfHello::default()));
+    assert_eq!(ospf_multicast_dest(&hello), ALL_SPF_ROUTERS);
+
+    let ack = Ospfv2Packet::new(
+        &id,
+        &id,
+        Ospfv2Payload::LsAck(OspfLsAck::from_headers(vec![])),
+    );
+    assert_eq!(ospf_multicast_dest(&ack), ALL_D_ROUTERS);
+}
+
+#[test]
+pub fn ls_type_conversion() {
+    for v in 1..=11u8 {
+        let ls_type = OspfLsType::try_from(v).unwrap();
+        assert_eq!(u8::from(ls_type), v);
+    }
+    assert_eq!(OspfLsType::try_from(6), Ok(OspfLsType::GroupMembership));
+    assert_eq!(OspfLsType::try_from(8), Ok(OspfLsType::Unassigned));
+    assert_eq!(OspfLsType::try_from(0), Err(0));
+    assert_eq!(OspfLsType::try_from(12), Err(12));
+    assert_eq!(u8::from(OspfLsType::Unknown(12)), 12);
+
+    assert!(OspfLsType::OpaqueLink.is_opaque());
+    assert!(OspfLsType::OpaqueArea.is_opaque());
+    assert!(OspfLsType::OpaqueAs.is_opaque());
+    assert!(!OspfLsType::NssaAsExternal.is_opaque());
+}
+
+#[test]
+pub fn options_bit_ops() {
+    let options = OspfOptions::default() | OspfOptions::from_bits(0x02);
+    assert!(options.external());
+    assert_eq!(options.to_bits(), 0x02);
+
+    let options = options | OspfOptions::new().with_nssa(true);
+    assert_eq!(options.to_bits(), 0x0a);
+    assert_eq!(
+        options & OspfOptions::from_bits(0x08),
+        OspfOptions::from_bits(0x08)
+    );
+    assert_eq!(
+        options ^ OspfOptions::from_bits(0x02),
+        OspfOptions::from_bits(0x08)
+    );
+    assert_eq!((!options).to_bits(), 0xf5);
+
+    let flags = DbDescFlags::new().with_init(true) | DbDescFlags::new().with_more(true);
+    let flags = flags | DbDescFlags::from_bits(0x01);
+    assert!(flags.is_all());
+    assert_eq!(flags.to_bits(), 0x07);
+    assert_eq!((flags ^ DbDescFlags::from_bits(0x04)).to_bits(), 0x03);
+    assert_eq!((flags & !DbDescFlags::from_bits(0x01)).to_bits(), 0x06);
+}
+
+#[test]
+pub fn parse_raw_lsa() {
+    let (_, packet) = parse(LS_UPD_MULTI).unwrap();
+    let Ospfv2Payload::LsUpdate(ref upd) = packet.payload else {
+        panic!("LS Update expected");
+    };
+
+    // LSAs follow OSPF header and # of LSAs.
+    let mut input = &LS_UPD_MULTI[28..];
+    for expected in upd.lsas.iter() {
+        let (_, h) = parse_lsa_header(input).unwrap();
+        assert_eq!(&h, &expected.h);
+
+        let (rem, lsa) = parse_lsa(input).unwrap();
+        assert_eq!(&lsa, expected);
+        assert_eq!(input.len() - rem.len(), h.length as usize);
+        input = rem;
+    }
+    assert!(input.is_empty());
+
+    // Router LSA alone.
+    let (rem, lsa) = parse_lsa(&LS_UPD_MULTI[28..28 + 0x30]).unwrap();
+    assert!(rem.is_empty());
+    assert!(matches!(lsa.lsa, OspfLsaPayload::Router(_)));
+
+    // Truncated LSA.
+    assert!(parse_lsa(&LS_UPD_MULTI[28..28 + 0x2f]).is_err());
+    assert!(parse_lsa_header(&LS_UPD_MULTI[28..28 + 19]).is_err());
+}
+
+#[test]
+pub fn lsa_default() {
+    let h = OspfLsaHeader {
+        ls_type: OspfLsType::Network,
+        ls_id: Ipv4Addr::new(10, 0, 0, 1),
+        ..Default::default()
+    };
+    assert_eq!(h.ls_age, 0);
+    assert_eq!(h.ls_type, OspfLsType::Network);
+    assert_eq!(h.adv_router, Ipv4Addr::UNSPECIFIED);
+    assert_eq!(h.ls_seq_number, 0x80000001);
+    assert_eq!(h.to_sequence_number(), SequenceNumber::INITIAL);
+    assert_eq!(h.length, 20);
+
+    let lsa = OspfLsa::default();
+    assert_eq!(lsa.h.ls_type, OspfLsType::Router);
+    assert!(matches!(lsa.lsa, OspfLsaPayload::Router(_)));
+
+    let upd = OspfLsUpdate::default();
+    assert_eq!(upd.num_adv, 0);
+    assert!(upd.lsas.is_empty());
+    assert!(OspfLsAck::default().lsa_headers.is_empty());
+    assert!(OspfLsRequest::default().reqs.is_empty());
+}
+
+#[test]
+pub fn ls_update_num_adv() {
+    let id = Ipv4Addr::new(10, 0, 0, 1);
+    let lsa = OspfLsa {
+        h: OspfLsaHeader::new(OspfLsType::Router, id, id),
+        lsa: OspfLsaPayload::Router(RouterLsa::default()),
+    };
+    let update = OspfLsUpdate {
+        num_adv: 0,
+        lsas: vec![lsa.clone(), lsa],
+    };
+    assert_eq!(update.num_lsas(), 2);
+
+    let packet = Ospfv2Packet::new(&id, &id, Ospfv2Payload::LsUpdate(update));
+    let buf = packet.emit_to_vec();
+    let (_, packet) = parse(&buf).unwrap();
+    let Ospfv2Payload::LsUpdate(update) = packet.payload else {
+        panic!("LS Update expected");
+    };
+    assert_eq!(update.num_adv, 2);
+    assert_eq!(update.num_lsas(), 2);
+}
+
+#[test]
+pub fn ls_request_entry_typed() {
+    let ls_id = Ipv4Addr::new(192, 168, 10, 0);
+    let adv_router = Ipv4Addr::new(4, 4, 4, 4);
+    let entry = OspfLsRequestEntry::new(OspfLsType::Summary, ls_id, adv_router);
+    assert_eq!(entry.ls_type, 3);
+    assert_eq!(entry.typed_ls_type(), OspfLsType::Summary);
+    assert_eq!(entry.ls_id_addr(), ls_id);
+
+    let output = entry.to_string();
+    assert!(output.contains("LS Type: Summary\n"));
+    assert!(output.contains("LS ID: 192.168.10.0\n"));
+
+    let entry = OspfLsRequestEntry {
+        ls_type: 12,
+        ls_id,
+        adv_router,
+    };
+    assert_eq!(entry.typed_ls_type(), OspfLsType::Unknown(12));
+
+    // LS type out of 8-bit range is not truncated to Router.
+    let entry = OspfLsRequestEntry {
+        ls_type: 0x101,
+        ls_id,
+        adv_router,
+    };
+    assert_eq!(entry.typed_ls_type(), OspfLsType::Unknown(1));
+    assert_ne!(entry.typed_ls_type(), OspfLsType::Router);
+}
+
+#[test]
+pub fn parse_hello_lls() {
+    const LLS: &[u8] = &hex!(
+        "
+        ff f6 00 03 00 01 00 04 00 00 00 01
+        "
+    );
+    let (rem, lls) = parse_lls(LLS, 0).unwrap();
+    assert!(rem.is_empty());
+
+    // Checksum mismatch, which is not checked with cryptographic
+    // authentication.
+    let mut bad = LLS.to_vec();
+    bad[1] = 0xf7;
+    assert!(parse_lls(&bad, 0).is_err());
+    assert!(parse_lls(&bad, 1).is_err());
+    assert!(parse_lls(&bad, 2).is_ok());
+    assert_eq!(lls.checksum, 0xfff6);
+    assert_eq!(lls.length, 3);
+    assert_eq!(lls.tlvs.len(), 1);
+    assert_eq!(lls.extended_options(), Some(1));
+
+    let id = Ipv4Addr::new(10, 0, 0, 1);
+    let hello = OspfHello {
+        options: OspfOptions::new().with_external(true).with_lls_data(true),
+        ..Default::default()
+    };
+    let mut packet = Ospfv2Packet::new(&id, &id, Ospfv2Payload::Hello(hello));
+    packet.lls = Some(OspfLlsBlock::new(vec![OspfLlsTlv::extended_options(1)]));
+    assert!(packet.has_lls());
+
+    let buf = packet.emit_to_vec();
+    assert_eq!(packet.byte_len(), buf.len());
+    assert_eq!(&buf[buf.len() - LLS.len()..], LLS);
+
+    // parse() leaves LLS data block.
+    let (rem, parsed) = parse(&buf).unwrap();
+    assert_eq!(rem, LLS);
+    assert!(parsed.lls.is_none());
+
+    let (rem, parsed) = parse_validated(&buf).unwrap();
+    assert!(rem.is_empty());
+    assert_eq!(parsed.lls, Some(lls));
+    assert_eq!(parsed.emit_to_vec(), buf);
+
+    let tlv = OspfLlsTlv::new(LLS_CRYPT_AUTH, hex!("00 00 00 05 01 02 03").to_vec());
+    assert_eq!(
+        tlv.value(),
+        OspfLlsTlvValue::CryptAuth {
+            sequence_number: 5,
+            auth_data: vec![1, 2, 3],
+        }
+    );
+    assert_eq!(tlv.byte_len(), 12);
+}
+
+#[test]
+pub fn parse_grace_lsa_tlvs() {
+    const LSA: &[u8] = &hex!(
+        "
+        00 01 02 09 03 00 00 00 01 01 01 01 80 00 00 01
+        e0 6b 00 2c 00 01 00 04 00 00 00 78 00 02 00 01
+        01 00 00 00 00 03 00 04 c0 a8 01 01
+        "
+    );
+    let (rem, lsa) = parse_lsa(LSA).unwrap();
+    assert!(rem.is_empty());
+    let OspfLsaPayload::OpaqueLink(ref opaque) = lsa.lsa else {
+        panic!("Opaque Link LSA expected");
+    };
+    assert_eq!(opaque.opaque_type, OPAQUE_TYPE_GRACE);
+
+    let grace = lsa.lsa.as_grace_lsa().unwrap();
+    assert_eq!(grace.grace_period, 120);
+    assert_eq!(grace.reason, GraceReason::SoftwareRestart);
+    assert_eq!(grace.ip_address, Some(Ipv4Addr::new(192, 168, 1, 1)));
+    assert_eq!(parse_grace_lsa(&opaque.data).unwrap().1, grace);
+
+    let output = lsa.to_string();
+    assert!(output.contains("Grace period: 120"));
+    assert!(output.contains("Reason: Software restart"));
+    assert!(output.contains("IP interface address: 192.168.1.1"));
+
+    // Not a Grace LSA.
+    let opaque = OpaqueLsa::new(1, 0, opaque.data.clone());
+    assert!(OspfLsaPayload::OpaqueLink(opaque).as_grace_lsa().is_none());
+}
+
+#[test]
+pub fn parse_grace_lsa_link_scope_only() {
+    // Same TLVs as Grace LSA but carried in Opaque Area LSA (type 10).
+    const LSA: &[u8] = &hex!(
+        "
+        00 01 02 0a 03 00 00 00 01 01 01 01 80 00 00 01
+        00 00 00 2c 00 01 00 04 00 00 00 78 00 02 00 01
+        01 00 00 00 00 03 00 04 c0 a8 01 01
+        "
+    );
+    let (rem, lsa) = parse_lsa(LSA).unwrap();
+    assert!(rem.is_empty());
+    let OspfLsaPayload::OpaqueArea(ref opaque) = lsa.lsa else {
+        panic!("Opaque Area LSA expected");
+    };
+    assert_eq!(opaque.opaque_type, OPAQUE_TYPE_GRACE);
+    assert!(lsa.lsa.as_grace_lsa().is_none());
+    assert!(!lsa.to_string().contains("Grace period"));
+}
+
+#[test]
+pub fn parse_te_lsa_tlvs() {
+    const ROUTER_ADDRESS: &[u8] = &hex!(
+        "
+        00 01 00 0a 01 00 00 00 0a 00 00 01 80 00 00 01
+        26 16 00 1c 00 01 00 04 0a 00 00 01
+        "
+    );
+    const LINK: &[u8] = &hex!(
+        "
+        00 01 00 0a 01 00 00 01 0a 00 00 01 80 00 00 01
+        78 9f 00 7c 00 02 00 64 00 01 00 01 01 00 00 00
+        00 02 00 04 0a 00 00 02 00 03 00 04 c0 a8 00 01
+        00 04 00 04 c0 a8 00 02 00 05 00 04 00 00 00 0a
+        00 06 00 04 4c ee 6b 28 00 07 00 04 4c ee 6b 28
+        00 08 00 20 4c ee 6b 28 4c ee 6b 28 4c ee 6b 28
+        4c ee 6b 28 4c ee 6b 28 4c ee 6b 28 4c ee 6b 28
+        4c ee 6b 28 00 09 00 04 00 00 00 01
+        "
+    );
+    let (_, lsa) = parse_lsa_strict(ROUTER_ADDRESS).unwrap();
+    let OspfLsaPayload::OpaqueArea(ref opaque) = lsa.lsa else {
+        panic!("Opaque Area LSA expected");
+    };
+    let te = opaque.as_te_lsa().unwrap();
+    assert_eq!(te.router_address, Some(Ipv4Addr::new(10, 0, 0, 1)));
+    assert!(te.links.is_empty());
+
+    let (_, lsa) = parse_lsa_strict(LINK).unwrap();
+    let OspfLsaPayload::OpaqueArea(ref opaque) = lsa.lsa else {
+        panic!("Opaque Area LSA expected");
+    };
+    assert_eq!(opaque.opaque_type, OPAQUE_TYPE_TE);
+    assert_eq!(opaque.opaque_id, 1);
+    let te = opaque.as_te_lsa().unwrap();
+    assert_eq!(te.router_address, None);
+    assert_eq!(te.links.len(), 1);
+
+    let link = &te.links[0];
+    assert_eq!(link.link_type, Some(1));
+    assert_eq!(link.link_id, Some(Ipv4Addr::new(10, 0, 0, 2)));
+    assert_eq!(link.local_addrs, vec![Ipv4Addr::new(192, 168, 0, 1)]);
+    assert_eq!(link.remote_addrs, vec![Ipv4Addr::new(192, 168, 0, 2)]);
+    assert_eq!(link.te_metric, Some(10));
+    assert_eq!(link.max_bandwidth, Some(125_000_000.0));
+    assert_eq!(link.max_resv_bandwidth, Some(125_000_000.0));
+    assert_eq!(link.unresv_bandwidth, Some([125_000_000.0; 8]));
+    assert_eq!(link.admin_group, Some(1));
+    assert_eq!(parse_te_lsa(&opaque.data).unwrap().1, te);
+
+    assert!(lsa.lsa.as_grace_lsa().is_none());
+}
+
+#[test]
+pub fn hello_compatibility() {
+    let local = OspfHello {
+        netmask: Ipv4Addr::new(255, 255, 255, 0),
+        hello_interval: 10,
+        options: OspfOptions::new().with_external(true),
+        router_dead_interval: 40,
+        ..Default::default()
+    };
+    let mut remote = local.clone();
+    remote.priority = 1;
+    remote.neighbors.push(Ipv4Addr::new(10, 0, 0, 1));
+    assert!(local.is_compatible_with(&remote));
+    assert_eq!(local.compatibility_error(&remote), None);
+
+    let mut remote = local.clone();
+    remote.hello_interval = 5;
+    remote.router_dead_interval = 20;
+    assert!(!local.is_compatible_with(&remote));
+    assert_eq!(
+        local.compatibility_error(&remote),
+        Some(HelloCompatibilityError::HelloInterval {
+            local: 10,
+            remote: 5
+        })
+    );
+
+    let mut remote = local.clone();
+    remote.router_dead_interval = 120;
+    assert_eq!(
+        local.compatibility_error(&remote),
+        Some(HelloCompatibilityError::RouterDeadInterval {
+            local: 40,
+            remote: 120
+        })
+    );
+
+    let mut remote = local.clone();
+    remote.options = OspfOptions::new().with_nssa(true);
+    assert_eq!(
+        local.compatibility_error(&remote),
+        Some(HelloCompatibilityError::Options {
+            local: local.options,
+            remote: remote.options
+        })
+    );
+
+    let mut remote = local.clone();
+    remote.netmask = Ipv4Addr::new(255, 255, 255, 252);
+    assert_eq!(
+        local.compatibility_error(&remote),
+        Some(HelloCompatibilityError::Netmask {
+            local: Ipv4Addr::new(255, 255, 255, 0),
+            remote: Ipv4Addr::new(255, 255, 255, 252)
+        })
+    );
+}
+
+#[test]
+pub fn network_lsa_prefix() {
+    let network = NetworkLsa {
+        netmask: Ipv4Addr::new(255, 255, 255, 0),
+        attached_routers: vec![],
+    };
+    let dr = Ipv4Addr::new(192, 168, 170, 3);
+    assert_eq!(network.prefix_length(), 24);
+    assert_eq!(network.network_address(dr), Ipv4Addr::new(192, 168, 170, 0));
+    assert_eq!(network.to_prefix(dr), (Ipv4Addr::new(192, 168, 170, 0), 24));
+
+    let network = NetworkLsa {
+        netmask: Ipv4Addr::new(255, 255, 255, 252),
+        attached_routers: vec![],
+    };
+    let dr = Ipv4Addr::new(10, 0, 0, 6);
+    assert_eq!(network.prefix_length(), 30);
+    assert_eq!(network.to_prefix(dr), (Ipv4Addr::new(10, 0, 0, 4), 30));
+}
+
+#[test]
+pub fn router_lsa_links_by_type() {
+    let (_, packet) = parse(LS_UPD_MULTI).unwrap();
+    let Ospfv2Payload::LsUpdate(ref upd) = packet.payload else {
+        panic!("LS Update expected");
+    };
+    let OspfLsaPayload::Router(ref router) = upd.lsas[0].lsa else {
+        panic!("Router LSA expected");
+    };
+    let prefix = Ipv4Addr::new(192, 168, 170, 0);
+    let mask = Ipv4Addr::new(255, 255, 255, 0);
+    let stubs: Vec<_> = router.stub_networks().collect();
+    assert_eq!(stubs, vec![(prefix, mask, 10), (prefix, mask, 10)]);
+    assert_eq!(router.transit_neighbors().count(), 0);
+    assert_eq!(router.point_to_point_neighbors().count(), 0);
+
+    let dr = Ipv4Addr::new(10, 0, 0, 1);
+    let local = Ipv4Addr::new(10, 0, 0, 2);
+    let neighbor = Ipv4Addr::new(2, 2, 2, 2);
+    let router = RouterLsa::builder()
+        .add_transit_link(dr, local, 5)
+        .add_p2p_link(neighbor, local, 20)
+        .add_stub_link(prefix, mask, 10)
+        .build();
+    let transit: Vec<_> = router.transit_neighbors().collect();
+    assert_eq!(transit, vec![(dr, local, 5)]);
+    let p2p: Vec<_> = router.point_to_point_neighbors().collect();
+    assert_eq!(p2p, vec![(neighbor, local, 20)]);
+    assert_eq!(router.stub_networks().count(), 1);
+}
+
+#[test]
+pub fn ls_update_find_lsa() {
+    let (_, packet) = parse(LS_UPD_MULTI).unwrap();
+    let Ospfv2Payload::LsUpdate(upd) = packet.payload else {
+        panic!("LS Update expected");
+    };
+    let router_id = Ipv4Addr::new(192, 168, 170, 3);
+    let key = OspfLsaKey {
+        ls_type: OspfLsType::Router,
+        ls_id: u32::from(router_id),
+        adv_router: router_id,
+    };
+    let lsa = upd.find_lsa(&key).unwrap();
+    assert_eq!(lsa, &upd.lsas[0]);
+    assert_eq!(upd.find_lsa_header(&key), Some(&upd.lsas[0].h));
+
+    let key = upd.lsas[3].h.key();
+    assert_eq!(upd.find_lsa(&key), Some(&upd.lsas[3]));
+
+    let missing = OspfLsaKey {
+        ls_type: OspfLsType::Network,
+        ls_id: u32::from(router_id),
+        adv_router: router_id,
+    };
+    assert!(upd.find_lsa(&missing).is_none());
+    assert!(upd.find_lsa_header(&missing).is_none());
+
+    let ack = OspfLsAck::from_headers(vec![upd.lsas[1].h.clone()]);
+    let mut h = upd.lsas[1].h.clone();
+    h.ls_seq_number += 1;
+    assert!(ack.contains(&h));
+    assert!(!ack.contains(&upd.lsas[2].h));
+
+    let lsas = upd.lsas.clone();
+    let map = upd.into_lsa_map();
+    assert_eq!(map.len(), lsas.len());
+    for lsa in lsas.iter() {
+        assert_eq!(map.get(&lsa.h.key()), Some(lsa));
+    }
+}
+
+#[test]
+pub fn db_desc_split_into_pages() {
+    let router_id = Ipv4Addr::new(10, 0, 0, 1);
+    let mut builder = OspfDbDesc::builder()
+        .mtu(1500)
+        .flags(DbDescFlags::new().with_master(true))
+        .sequence_number(100);
+    for i in 0..10 {
+        let id = Ipv4Addr::new(10, 0, 1, i);
+        builder = builder.add_lsa_header(OspfLsaHeader::new(OspfLsType::Router, id, router_id));
+    }
+    let dd = builder.build();
+
+    let pages = dd.split_into_pages(300);
+    assert_eq!(pages.len(), 1);
+    assert_eq!(pages[0].lsa_headers, dd.lsa_headers);
+    assert!(!pages[0].flags.more());
+    assert!(pages[0].flags.master());
+
+    let pages = dd.split_into_pages(100);
+    assert_eq!(pages.len(), 4);
+    let sizes: Vec<_> = pages.iter().map(|p| p.lsa_headers.len()).collect();
+    assert_eq!(sizes, vec![3, 3, 3, 1]);
+    for (i, page) in pages.iter().enumerate() {
+        assert_eq!(page.flags.more(), i != pages.len() - 1);
+        assert!(page.flags.master());
+        assert_eq!(page.seqnum, 100);
+        let packet = Ospfv2Packet::new(&router_id, &router_id, Ospfv2Payload::DbDesc(page.clone()));
+        assert!(packet.emit_to_vec().len() <= 100);
+    }
+    let headers: Vec<_> = pages.into_iter().flat_map(|p| p.lsa_headers).collect();
+    assert_eq!(headers, dd.lsa_headers);
+
+    let empty = OspfDbDesc::default().split_into_pages(1500);
+    assert_eq!(empty.len(), 1);
+    assert!(empty[0].lsa_headers.is_empty());
+}
+
+#[test]
+pub fn lsa_new_router() {
+    let router_id = Ipv4Addr::new(10, 0, 0, 1);
+    let links = vec![
+        RouterLsaLink::new("10.0.0.0/24".parse().unwrap(), 10),
+        RouterLsaLink::new("10.0.1.0/24".parse().unwrap(), 20),
+    ];
+    let lsa = OspfLsa::new_router(router_id, SequenceNumber::INITIAL, links);
+    assert_eq!(lsa.h.ls_type, OspfLsType::Router);
+    assert_eq!(lsa.h.ls_id, router_id);
+    assert_eq!(lsa.h.adv_router, router_id);
+    assert_eq!(lsa.h.ls_age, 0);
+    assert_eq!(lsa.h.to_sequence_number(), SequenceNumber::INITIAL);
+    assert!(lsa.h.options.external());
+
+    let buf = lsa.emit_to_vec();
+    let (rem, parsed) = parse_lsa_strict(&buf).unwrap();
+    assert!(rem.is_empty());
+    assert_eq!(parsed.h.length as usize, buf.len());
+    assert_eq!(parsed.lsa, lsa.lsa);
+    let OspfLsaPayload::Router(ref router) = parsed.lsa else {
+        panic!("Router LSA expected");
+    };
+    assert_eq!(router.num_links, 2);
+
+    let ls_id = Ipv4Addr::new(4, 0, 0, 1);
+    let opaque = OspfLsaPayload::OpaqueArea(OpaqueLsa::new(1, 0, vec![0, 1, 0, 4, 10, 0, 0, 1]));
+    let lsa = OspfLsa::new(router_id, ls_id, SequenceNumber::INITIAL, opaque);
+    assert_eq!(lsa.h.ls_type, OspfLsType::OpaqueArea);
+    let buf = lsa.emit_to_vec();
+    let (_, parsed) = parse_lsa_strict(&buf).unwrap();
+    let OspfLsaPayload::OpaqueArea(ref opaque) = parsed.lsa else {
+        panic!("Opaque Area LSA expected");
+    };
+    assert_eq!(opaque.ls_id(), ls_id);
+    assert_eq!(parsed.lsa, lsa.lsa);
+}
+
+#[test]
+pub fn lsa_payload_from() {
+    let router_id = Ipv4Addr::new(10, 0, 0, 1);
+    let netmask = Ipv4Addr::new(255, 255, 255, 0);
+
+    let payload: OspfLsaPayload = RouterLsa::default().into();
+    assert_eq!(payload.ls_type(), OspfLsType::Router);
+
+    let payload: OspfLsaPayload = NetworkLsa {
+        netmask,
+        attached_routers: vec![router_id],
+    }
+    .into();
+    assert_eq!(payload.ls_type(), OspfLsType::Network);
+
+    let payload: OspfLsaPayload = SummaryLsa {
+        netmask,
+        tos: 0,
+        metric: 10,
+        tos_routes: vec![],
+    }
+    .into();
+    assert!(matches!(payload, OspfLsaPayload::Summary(_)));
+    assert_eq!(payload.ls_type(), OspfLsType::Summary);
+
+    let payload: OspfLsaPayload = AsExternalLsa {
+        netmask,
+        ext_and_resvd: 0x80,
+        metric: 20,
+        forwarding_address: Ipv4Addr::UNSPECIFIED,
+        external_route_tag: 0,
+        tos_list: vec![],
+    }
+    .into();
+    assert_eq!(payload.ls_type(), OspfLsType::AsExternal);
+
+    let ls_id = Ipv4Addr::new(192, 168, 0, 0);
+    let lsa = OspfLsa::new(router_id, ls_id, SequenceNumber::INITIAL, payload);
+    assert_eq!(lsa.h.ls_type, OspfLsType::AsExternal);
+}
+
+#[test]
+pub fn parse_all_packets() {
+    const LS_UPD: &[u8] = &hex!(
+        "
+        02 04 00 40 c0 a8 aa 08 00 00 00 01 96 1f 00 00
+        00 00 00 00 00 00 00 00 00 00 00 01 03 e2 02 01
+        c0 a8 aa 08 c0 a8 aa 08 80 00 0d c3 25 06 00 24
+        02 00 00 01 c0 a8 aa 00 ff ff ff 00 03 00 00 0a
+        "
+    );
+    let mut buf = HELLO.to_vec();
+    buf.extend_from_slice(LS_UPD);
+
+    let packets = parse_all(&buf).unwrap();
+    assert_eq!(packets.len(), 2);
+    assert_eq!(packets[0].typ, OspfType::Hello);
+    assert_eq!(packets[1].typ, OspfType::LsUpdate);
+
+    let mut iter = OspfPacketIter::new(&buf);
+    assert_eq!(iter.next().unwrap().unwrap().typ, OspfType::Hello);
+    assert_eq!(iter.next().unwrap().unwrap().typ, OspfType::LsUpdate);
+    assert!(iter.next().is_none());
+
+    assert!(parse_all(&[]).unwrap().is_empty());
+
+    // Trailing garbage fails after the first packet.
+    let mut buf = HELLO.to_vec();
+    buf.extend_from_slice(&LS_UPD[..20]);
+    assert_eq!(parse_all(&buf), Err(OspfError::Truncated));
+
+    let mut iter = OspfPacketIter::new(&buf);
+    assert!(iter.next().unwrap().is_ok());
+    assert_eq!(iter.next(), Some(Err(OspfError::Truncated)));
+    assert!(iter.next().is_none());
+}
+
+#[test]
+pub fn parse_all_packets_lls() {
+    const LS_UPD: &[u8] = &hex!(
+        "
+        02 04 00 40 c0 a8 aa 08 00 00 00 01 96 1f 00 00
+        00 00 00 00 00 00 00 00 00 00 00 01 03 e2 02 01
+        c0 a8 aa 08 c0 a8 aa 08 80 00 0d c3 25 06 00 24
+        02 00 00 01 c0 a8 aa 00 ff ff ff 00 03 00 00 0a
+        "
+    );
+    let id = Ipv4Addr::new(10, 0, 0, 1);
+    let hello = OspfHello {
+        options: OspfOptions::new().with_external(true).with_lls_data(true),
+        ..Default::default()
+    };
+    let mut hello = Ospfv2Packet::new(&id, &id, Ospfv2Payload::Hello(hello));
+    let lls = OspfLlsBlock::new(vec![OspfLlsTlv::extended_options(1)]);
+    hello.lls = Some(lls.clone());
+
+    // Hello with LLS, LS Update, and Hello with LLS again.
+    let mut buf = hello.emit_to_vec();
+    buf.extend_from_slice(LS_UPD);
+    buf.extend_from_slice(&hello.emit_to_vec());
+
+    let packets = parse_all(&buf).unwrap();
+    assert_eq!(packets.len(), 3);
+    assert_eq!(packets[0].typ, OspfType::Hello);
+    assert_eq!(packets[0].lls.as_ref().unwrap().tlvs, lls.tlvs);
+    assert_eq!(packets[1].typ, OspfType::LsUpdate);
+    assert!(packets[1].lls.is_none());
+    assert_eq!(packets[2].typ, OspfType::Hello);
+    assert!(packets[2].lls.is_some());
+
+    // LLS data block is truncated.
+    let buf = hello.emit_to_vec();
+    assert!(parse_all(&buf[..buf.len() - 4]).is_err());
+}
+
+#[test]
+pub fn parse_valid_packet() {
+    assert_eq!(is_valid(HELLO), Ok(()));
+    let packet = parse_valid(HELLO).unwrap();
+    assert_eq!(packet.typ, OspfType::Hello);
+
+    assert_eq!(is_valid(&HELLO[..20]), Err(OspfError::Truncated));
+
+    let mut buf = HELLO.to_vec();
+    buf[0] = 3;
+    assert_eq!(is_valid(&buf), Err(OspfError::UnknownVersion(3)));
+
+    // Trailing bytes after the packet are rejected.
+    let mut buf = HELLO.to_vec();
+    buf.push(0);
+    assert_eq!(
+        is_valid(&buf),
+        Err(OspfError::Length {
+            expected: 44,
+            actual: 45
+        })
+    );
+
+    let mut buf = HELLO.to_vec();
+    buf[27] = 0x14;
+    assert_eq!(
+        parse_valid(&buf),
+        Err(OspfError::Checksum {
+            expected: 0x2727,
+            actual: 0x273b
+        })
+    );
+
+    let mut buf = HELLO.to_vec();
+    buf[15] = 3;
+    assert_eq!(is_valid(&buf), Err(OspfError::InvalidAuthType(3)));
+
+    // MD5 authentication, the message digest follows the packet.
+    const MD5_PACKET: &[u8] = &hex!(
+        "
+        02 01 00 2c c0 a8 0a 01 00 00 00 00 00 00 00 02
+        00 00 01 10 5f 00 00 01 ff ff ff 00 00 0a 02 01
+        00 00 00 28 c0 a8 0a 01 00 00 00 00 03 89 9e 37
+        8c 4f 85 e2 d9 e5 36 37 de 92 b0 c0
+        "
+    );
+    assert_eq!(is_valid(MD5_PACKET), Ok(()));
+    let packet = parse_valid(MD5_PACKET).unwrap();
+    assert_eq!(packet.digest.len(), 16);
+    assert_eq!(
+        is_valid(&MD5_PACKET[..59]),
+        Err(OspfError::Length {
+            expected: 60,
+            actual: 59
+        })
+    );
+}
+
+#[test]
+pub fn display_options_and_flags() {
+    assert_eq!(OspfOptions::new().to_string(), "None");
+    assert_eq!(OspfOptions::new().with_external(true).to_string(), "E");
+    assert_eq!(
+        OspfOptions::new()
+            .with_external(true)
+            .with_nssa(true)
+            .to_string(),
+        "E,N"
+    );
+    assert_eq!(
+        OspfOptions::new()
+            .with_external(true)
+            .with_lls_data(true)
+            .with_o(true)
+            .to_string(),
+        "E,LLS,O"
+    );
+
+    assert_eq!(DbDescFlags::new().to_string(), "None");
+    assert_eq!(DbDescFlags::new().with_more(true).to_string(), "M");
+    assert_eq!(DbDescFlags::from_bits(0x07).to_string(), "MS,M,I");
+    assert_eq!(
+        DbDescFlags::new()
+            .with_master(true)
+            .with_oob_resync(true)
+            .to_string(),
+        "MS,OOB"
+    );
+
+    let dd = OspfDbDesc {
+        options: OspfOptions::new().with_external(true),
+        flags: DbDescFlags::from_bits(0x07),
+        ..Default::default()
+    };
+    let s = dd.to_string();
+    assert!(s.contains(" Options: E\n"));
+    assert!(s.contains(" Flags: I|M|MS|-\n"));
+}
+
+#[test]
+pub fn db_desc_flag_shortcuts() {
+    let dd = OspfDbDesc {
+        flags: DbDescFlags::from_bits(0x07),
+        ..Default::default()
+    };
+    assert!(dd.is_initial_exchange());
+    assert!(dd.is_master());
+    assert!(dd.has_more());
+    assert!(dd.is_init());
+    assert!(!dd.is_empty_exchange());
+
+    let dd = OspfDbDesc {
+        flags: DbDescFlags::new().with_more(true),
+        lsa_headers: vec![OspfLsaHeader::default()],
+        ..Default::default()
+    };
+    assert!(!dd.is_initial_exchange());
+    assert!(!dd.is_master());
+    assert!(dd.has_more());
+    assert!(!dd.is_init());
+    assert!(!dd.is_empty_exchange());
+
+    let dd = OspfDbDesc {
+        flags: DbDescFlags::new().with_master(true),
+        ..Default::default()
+    };
+    assert!(dd.is_master());
+    assert!(dd.is_empty_exchange());
+
+    let dd = OspfDbDesc {
+        lsa_headers: vec![OspfLsaHeader::default()],
+        ..Default::default()
+    };
+    assert!(!dd.is_empty_exchange());
+}
+
+#[test]
+pub fn as_external_metric_type() {
+    const LSA: &[u8] = &hex!(
+        "
+        00 01 02 05 c0 a8 01 00 02 02 02 02 80 00 00 01
+        00 00 00 24 ff ff ff 00 00 00 00 14 00 00 00 00
+        00 00 00 00
+        "
+    );
+    let (_, lsa) = parse_lsa(LSA).unwrap();
+    let OspfLsaPayload::AsExternal(ref ext) = lsa.lsa else {
+        panic!("not AS External LSA");
+    };
+    assert!(!ext.is_type2_metric());
+    assert_eq!(ext.metric_type(), AsExternalMetricType::Type1);
+    assert!(!ext.forwarding_address_is_set());
+    assert_eq!(ext.metric, 20);
+    assert!(ext.to_string().contains("Metric type: 1\n"));
+
+    let mut ext = ext.clone();
+    ext.ext_and_resvd = 0x80;
+    ext.forwarding_address = Ipv4Addr::new(192, 168, 10, 1);
+    assert!(ext.is_type2_metric());
+    assert_eq!(ext.metric_type(), AsExternalMetricType::Type2);
+    assert!(ext.forwarding_address_is_set());
+    assert!(ext.to_string().contains("Metric type: 2\n"));
+}
+
+#[test]
+pub fn lsa_header_age_by() {
+    let id = Ipv4Addr::new(10, 0, 0, 1);
+    let mut h = OspfLsaHeader::new(OspfLsType::Router, id, id);
+    assert_eq!(h.seconds_until_maxage(), LSA_MAX_AGE);
+    h.age_by(1000);
+    assert_eq!(h.ls_age, 1000);
+    assert_eq!(h.seconds_until_maxage(), 2600);
+    h.age_by(2599);
+    assert_eq!(h.ls_age, 3599);
+    assert_eq!(h.seconds_until_maxage(), 1);
+    h.age_by(10);
+    assert_eq!(h.ls_age, 3600);
+    assert_eq!(h.seconds_until_maxage(), 0);
+    h.age_by(u16::MAX);
+    assert_eq!(h.ls_age, LSA_MAX_AGE);
+
+    h.ls_age = LSA_DO_NOT_AGE | 200;
+    h.age_by(1000);
+    assert_eq!(h.ls_age, LSA_DO_NOT_AGE | 200);
+    assert_eq!(h.seconds_until_maxage(), 3400);
+}
+
+#[test]
+pub fn lsa_retire() {
+    let router_id = Ipv4Addr::new(10, 0, 0, 1);
+    let links = vec![RouterLsaLink::new("192.168.0.0/24".parse().unwrap(), 10)];
+    let mut lsa = OspfLsa::new_router(router_id, SequenceNumber::INITIAL, links);
+    lsa.h.ls_age = 100;
+    assert!(!lsa.is_retired());
+
+    lsa.retire();
+    assert!(lsa.is_retired());
+    assert_eq!(lsa.h.ls_age, LSA_MAX_AGE);
+
+    let buf = lsa.emit_to_vec();
+    let (rem, parsed) = parse_lsa_strict(&buf).unwrap();
+    assert!(rem.is_empty());
+    assert!(parsed.h.is_maxage());
+    assert!(parsed.is_retired());
+
+    let mut h = OspfLsaHeader::new(OspfLsType::Router, router_id, router_id);
+    h.ls_age = LSA_DO_NOT_AGE | 10;
+    h.retire();
+    assert!(h.is_maxage());
+    assert!(!h.is_do_not_age());
+}
+
+#[test]
+pub fn ls_update_into_lsa_ack() {
+    let (_, packet) = parse(LS_UPD_MULTI).unwrap();
+    let Ospfv2Payload::LsUpdate(upd) = packet.payload else {
+        panic!("not LS Update");
+    };
+    let ack = upd.as_lsa_ack();
+    assert_eq!(ack.lsa_headers.len(), 7);
+
+    let headers: Vec<OspfLsaHeader> = upd.lsas.iter().map(|lsa| lsa.h.clone()).collect();
+    let consumed = upd.into_lsa_ack();
+    assert_eq!(consumed, ack);
+    assert_eq!(consumed.lsa_headers, headers);
+
+    let packet = Ospfv2Packet::new(
+        &packet.router_id,
+        &packet.area_id,
+        Ospfv2Payload::LsAck(consumed),
+    );
+    let buf = packet.emit_to_vec();
+    assert_eq!(buf.len(), 24 + 7 * 20);
+}
+
+#[test]
+pub fn summary_lsa_prefix() {
+    let summary = SummaryLsa {
+        netmask: Ipv4Addr::new(255, 255, 255, 0),
+        tos: 0,
+        metric: 20,
+        tos_routes: vec![],
+    };
+    assert_eq!(
+        summary.to_prefix(Ipv4Addr::new(192, 168, 1, 0)),
+        (Ipv4Addr::new(192, 168, 1, 0), 24)
+    );
+    assert_eq!(summary.metric_value(), 20);
+
+    let summary = SummaryLsa {
+        netmask: Ipv4Addr::new(255, 255, 0, 0),
+        tos: 0,
+        metric: 0x00ff_ffff,
+        tos_routes: vec![],
+    };
+    assert_eq!(
+        summary.to_prefix(Ipv4Addr::new(172, 16, 5, 1)),
+        (Ipv4Addr::new(172, 16, 0, 0), 16)
+    );
+    assert_eq!(summary.metric_value(), 0x00ff_ffff);
+
+    // Default summary route.
+    let summary = SummaryLsa {
+        netmask: Ipv4Addr::UNSPECIFIED,
+        tos: 0,
+        metric: 1,
+        tos_routes: vec![],
+    };
+    assert_eq!(
+        summary.to_prefix(Ipv4Addr::UNSPECIFIED),
+        (Ipv4Addr::UNSPECIFIED, 0)
+    );
+
+    let nssa = NssaAsExternalLsa {
+        netmask: Ipv4Addr::new(255, 255, 255, 0),
+        ext_and_resvd: 0x80,
+        metric: 100,
+        forwarding_address: Ipv4Addr::UNSPECIFIED,
+        external_route_tag: 0,
+        tos_list: vec![],
+    };
+    assert_eq!(
+        nssa.to_prefix(Ipv4Addr::new(10, 1, 2, 3)),
+        (Ipv4Addr::new(10, 1, 2, 0), 24)
+    );
+    assert_eq!(nssa.metric_value(), 100);
+
+    let nssa = NssaAsExternalLsa {
+        netmask: Ipv4Addr::new(255, 255, 0, 0),
+        ..nssa
+    };
+    assert_eq!(
+        nssa.to_prefix(Ipv4Addr::new(10, 1, 2, 3)),
+        (Ipv4Addr::new(10, 1, 0, 0), 16)
+    );
+    let nssa = NssaAsExternalLsa {
+        netmask: Ipv4Addr::UNSPECIFIED,
+        ..nssa
+    };
+    assert_eq!(
+        nssa.to_prefix(Ipv4Addr::UNSPECIFIED),
+        (Ipv4Addr::UNSPECIFIED, 0)
+    );
+}
+
+#[test]
+pub fn ospf_type_conversion() {
+    let types = [
+        (1, OspfType::Hello),
+        (2, OspfType::DbDesc),
+        (3, OspfType::LsRequest),
+        (4, OspfType::LsUpdate),
+        (5, OspfType::LsAck),
+    ];
+    for (val, typ) in types {
+        assert_eq!(OspfType::try_from(val), Ok(typ));
+        assert_eq!(u8::from(typ), val);
+        assert_eq!(typ.as_u8(), val);
+    }
+    for val in (0..=u8::MAX).filter(|v| !(1..=5).contains(v)) {
+        assert_eq!(OspfType::try_from(val), Err(val));
+        assert_eq!(u8::from(OspfType::Unknown(val)), val);
+    }
+
+    const LS_ACK: u8 = OspfType::LsAck.as_u8();
+    assert_eq!(LS_ACK, 5);
+
+    let (_, typ) = OspfType::parse_be(&[6]).unwrap();
+    assert_eq!(typ, OspfType::Unknown(6));
+}
+
+#[test]
+pub fn ls_type_flooding_scope() {
+    for val in 1..=11u8 {
+        let ls_type = OspfLsType::try_from(val).unwrap();
+        assert_eq!(u8::from(ls_type), val);
+        assert_eq!(ls_type.is_opaque(), (9..=11).contains(&val));
+    }
+    assert_eq!(OspfLsType::try_from(0), Err(0));
+    assert_eq!(OspfLsType::try_from(12), Err(12));
+
+    assert_eq!(
+        OspfLsType::OpaqueLink.flooding_scope(),
+        FloodingScope::LinkLocal
+    );
+    assert_eq!(OspfLsType::Router.flooding_scope(), FloodingScope::Area);
+    assert_eq!(OspfLsType::Summary.flooding_scope(), FloodingScope::Area);
+    assert_eq!(
+        OspfLsType::NssaAsExternal.flooding_scope(),
+        FloodingScope::Area
+    );
+    assert_eq!(OspfLsType::OpaqueArea.flooding_scope(), FloodingScope::Area);
+    assert_eq!(OspfLsType::AsExternal.flooding_scope(), FloodingScope::As);
+    assert_eq!(OspfLsType::OpaqueAs.flooding_scope(), FloodingScope::As);
+    assert_eq!(
+        OspfLsType::Unassigned.flooding_scope(),
+        FloodingScope::Unknown
+    );
+    assert_eq!(
+        OspfLsType::Unknown(12).flooding_scope(),
+        FloodingScope::Unknown
+    );
+}
+
+#[test]
+pub fn hello_neighbors() {
+    let nbr1 = Ipv4Addr::new(10, 0, 0, 1);
+    let nbr2 = Ipv4Addr::new(10, 0, 0, 2);
+    let mut hello = OspfHello::default();
+
+    hello.add_neighbor(nbr1);
+    hello.add_neighbor(nbr1);
+    assert_eq!(hello.neighbors.len(), 1);
+    assert!(hello.has_neighbor(&nbr1));
+    assert!(!hello.has_neighbor(&nbr2));
+
+    hello.add_neighbor(nbr2);
+    assert_eq!(hello.neighbors, vec![nbr1, nbr2]);
+
+    hello.remove_neighbor(&nbr1);
+    assert_eq!(hello.neighbors.len(), 1);
+    assert!(!hello.has_neighbor(&nbr1));
+
+    hello.neighbors.push(nbr2);
+    hello.remove_neighbor(&nbr2);
+    assert!(hello.neighbors.is_empty());
+
+    hello.remove_neighbor(&nbr2);
+    assert!(hello.neighbors.is_empty());
+}
+
+#[test]
+pub fn db_desc_flags_exchange() {
+    let check = |flags: DbDescFlags, master: bool, more: bool, init: bool| {
+        assert_eq!(flags.master(), master);
+        assert_eq!(flags.more(), more);
+        assert_eq!(flags.init(), init);
+        assert!(!flags.oob_resync());
+    };
+    check(DbDescFlags::initial_master(), true, true, true);
+    check(DbDescFlags::initial_slave(), false, true, true);
+    check(DbDescFlags::continuing_master(), true, true, false);
+    check(DbDescFlags::continuing_slave(), false, true, false);
+    check(DbDescFlags::final_master(), true, false, false);
+    check(DbDescFlags::final_slave(), false, false, false);
+
+    assert!(DbDescFlags::initial_master().is_all());
+    assert_eq!(DbDescFlags::initial_master().to_bits(), 0x07);
+    assert_eq!(DbDescFlags::final_slave().to_bits(), 0x00);
+}
+
+#[test]
+pub fn ls_request_from_headers() {
+    let (_, packet) = parse(DB_DESC_LSA).unwrap();
+    let Ospfv2Payload::DbDesc(dd) = packet.payload else {
+        panic!("not DB Description");
+    };
+    let req = OspfLsRequest::from_db_desc(&dd);
+    assert_eq!(req.reqs.len(), 7);
+
+    let owned = OspfLsRequest::from_headers(dd.lsa_headers.clone());
+    assert_eq!(owned, req);
+
+    for (entry, h) in owned.reqs.iter().zip(dd.lsa_headers.iter()) {
+        assert_eq!(entry.ls_type, u8::from(h.ls_type) as u32);
+        assert_eq!(entry.ls_id, h.ls_id);
+        assert_eq!(entry.adv_router, h.adv_router);
+    }
+
+    let req = OspfLsRequest::from_headers(
+        dd.lsa_headers
+            .iter()
+            .filter(|h| h.ls_type == OspfLsType::Router),
+    );
+    assert_eq!(req.reqs.len(), 1);
+}
+
+#[test]
+pub fn lsa_same_lsa_and_duplicate() {
+    let router_id = Ipv4Addr::new(10, 0, 0, 1);
+    let links = vec![RouterLsaLink::new("10.0.0.0/24".parse().unwrap(), 10)];
+    let buf = OspfLsa::new_router(router_id, SequenceNumber::INITIAL, links).emit_to_vec();
+    let (_, lsa) = parse_lsa(&buf).unwrap();
+
+    // Same instance with a different LS age.
+    let mut other = lsa.clone();
+    other.h.ls_age = 100;
+    assert!(lsa.same_lsa_as(&other));
+    assert!(lsa.h.same_lsa_as(&other.h));
+    assert!(lsa.is_duplicate_of(&other));
+
+    // Newer instance of the same LSA.
+    let mut newer = lsa.clone();
+    newer.h.ls_seq_number += 1;
+    newer.h.ls_checksum = newer.h.ls_checksum.wrapping_add(1);
+    assert!(lsa.same_lsa_as(&newer));
+    assert!(!lsa.is_duplicate_of(&newer));
+
+    let mut other = lsa.clone();
+    other.h.ls_checksum = other.h.ls_checksum.wrapping_add(1);
+    assert!(!lsa.is_duplicate_of(&other));
+
+    // Different LSA.
+    let mut other = lsa.clone();
+    other.h.adv_router = Ipv4Addr::new(10, 0, 0, 2);
+    assert!(!lsa.same_lsa_as(&other));
+    assert!(!lsa.is_duplicate_of(&other));
+
+    let mut other = lsa.clone();
+    other.h.ls_id = Ipv4Addr::new(10, 0, 0, 2);
+    assert!(!lsa.h.same_lsa_as(&other.h));
+
+    let mut other = lsa.clone();
+    other.h.ls_type = OspfLsType::Network;
+    assert!(!lsa.h.same_lsa_as(&other.h));
+}
+
+#[test]
+pub fn emit_trait_objects() {
+    let id = Ipv4Addr::new(10, 0, 0, 1);
+    let h = OspfLsaHeader::new(OspfLsType::Router, id, id);
+    let hello = OspfHello {
+        neighbors: vec![id],
+        ..Default::default()
+    };
+    let dd = OspfDbDesc {
+        lsa_headers: vec![h.clone()],
+        ..Default::default()
+    };
+    let entry = h.to_request_entry();
+    let req = OspfLsRequest {
+        reqs: vec![entry.clone()],
+    };
+    let upd = OspfLsUpdate {
+        num_adv: 1,
+        lsas: vec![OspfLsa::new_router(id, SequenceNumber::INITIAL, vec![])],
+    };
+    let ack = OspfLsAck::from_headers(vec![h]);
+
+    let payloads: Vec<(&dyn Emit, usize)> = vec![
+        (&hello, 24),
+        (&dd, 8 + 20),
+        (&entry, 12),
+        (&req, 12),
+        (&upd, 4 + 24),
+        (&ack, 20),
+    ];
+    for (payload, len) in payloads {
+        let mut buf = BytesMut::new();
+        payload.emit(&mut buf);
+        assert_eq!(buf.len(), len);
+    }
+
+    let payload = Ospfv2Payload::LsRequest(req);
+    let mut buf = BytesMut::new();
+    payload.emit(&mut buf);
+    assert_eq!(buf.len(), 12);
+
+    let packet = Ospfv2Packet::new(&id, &Ipv4Addr::UNSPECIFIED, payload);
+    let (_, parsed) = parse(&packet.emit_to_vec()).unwrap();
+    assert_eq!(parsed.payload, packet.payload);
+}
+
+#[test]
+pub fn db_desc_page_count() {
+    assert_eq!(OspfDbDesc::max_headers_per_packet(1500), 73);
+    assert_eq!(OspfDbDesc::max_headers_per_packet(576), 27);
+    assert_eq!(OspfDbDesc::max_headers_per_packet(32), 1);
+
+    let router_id = Ipv4Addr::new(10, 0, 0, 1);
+    let dd_with = |count: u8| OspfDbDesc {
+        lsa_headers: (0..count)
+            .map(|i| {
+                let id = Ipv4Addr::new(10, 0, 1, i);
+                OspfLsaHeader::new(OspfLsType::Router, id, router_id)
+            })
+            .collect(),
+        ..Default::default()
+    };
+
+    for (count, pages_1500, pages_576) in [
+        (0, 1, 1),
+        (1, 1, 1),
+        (27, 1, 1),
+        (28, 1, 2),
+        (73, 1, 3),
+        (74, 2, 3),
+        (200, 3, 8),
+    ] {
+        let dd = dd_with(count);
+        assert_eq!(dd.lsa_header_page_count(1500), pages_1500);
+        assert_eq!(dd.lsa_header_page_count(576), pages_576);
+        assert_eq!(dd.split_into_pages(1500).len(), pages_1500);
+        assert_eq!(dd.split_into_pages(576).len(), pages_576);
+    }
+}
+
+#[test]
+pub fn router_lsa_link_constructors() {
+    let router_id = Ipv4Addr::new(10, 0, 0, 1);
+    let local_ip = Ipv4Addr::new(192, 168, 0, 1);
+    let links = vec![
+        RouterLsaLink::new_stub(
+            Ipv4Addr::new(192, 168, 1, 0),
+            Ipv4Addr::new(255, 255, 255, 0),
+            10,
+        ),
+        RouterLsaLink::new_transit(Ipv4Addr::new(192, 168, 0, 2), local_ip, 20),
+        RouterLsaLink::new_point_to_point(Ipv4Addr::new(10, 0, 0, 2), local_ip, 30),
+        RouterLsaLink::new_virtual(Ipv4Addr::new(10, 0, 0, 3), local_ip, 40),
+    ];
+    assert!(links[0].is_stub());
+    assert!(links[1].is_transit());
+    assert!(links[2].is_point_to_point());
+    assert!(links[3].is_virtual());
+    for link in links.iter() {
+        assert_eq!(link.num_tos, 0);
+        assert!(link.toses.is_empty());
+    }
+
+    let lsa = OspfLsa::new_router(router_id, SequenceNumber::INITIAL, links.clone());
+    let buf = lsa.emit_to_vec();
+    let (rem, parsed) = parse_lsa_strict(&buf).unwrap();
+    assert!(rem.is_empty());
+    let OspfLsaPayload::Router(router) = parsed.lsa else {
+        panic!("not Router LSA");
+    };
+    assert_eq!(router.links, links);
+    assert_eq!(router.links[1].link_id, Ipv4Addr::new(192, 168, 0, 2));
+    assert_eq!(router.links[1].link_data, local_ip);
+    assert_eq!(router.links[3].tos_0_metric, 40);
+}
+
+#[test]
+pub fn hello_validate_intervals() {
+    let hello = |hello_interval: u16, router_dead_interval: u32| OspfHello {
+        hello_interval,
+        router_dead_interval,
+        ..Default::default()
+    };
+    assert_eq!(hello(10, 40).validate_intervals(), Ok(()));
+    assert_eq!(hello(10, 10).validate_intervals(), Ok(()));
+    assert_eq!(hello(1, 3).validate_intervals(), Ok(()));
+    assert_eq!(
+        hello(0, 40).validate_intervals(),
+        Err(HelloValidationError::ZeroHelloInterval)
+    );
+    assert_eq!(
+        hello(10, 5).validate_intervals(),
+        Err(HelloValidationError::DeadIntervalTooSmall)
+    );
+    assert_eq!(
+        hello(10, 0).validate_intervals(),
+        Err(HelloValidationError::DeadIntervalTooSmall)
+    );
+    assert_eq!(
+        hello(10, 45).validate_intervals(),
+        Err(HelloValidationError::NotMultiple)
+    );
+}
+
+#[test]
+pub fn ls_update_count_by_type() {
+    let (_, packet) = parse(LS_UPD_MULTI).unwrap();
+    let Ospfv2Payload::LsUpdate(upd) = packet.payload else {
+        panic!("not LS Update");
+    };
+    let counts = upd.count_by_type();
+    assert_eq!(counts.len(), 2);
+    assert_eq!(counts[&OspfLsType::Router], 1);
+    assert_eq!(counts[&OspfLsType::AsExternal], 6);
+    assert_eq!(counts.values().sum::<usize>(), upd.num_lsas());
+
+    assert!(upd.contains_type(OspfLsType::AsExternal));
+    assert!(!upd.contains_type(OspfLsType::Network));
+
+    assert_eq!(upd.lsas_of_type(OspfLsType::AsExternal).count(), 6);
+    assert!(upd
+        .lsas_of_type(OspfLsType::Router)
+        .all(|lsa| lsa.h.adv_router == Ipv4Addr::new(192, 168, 170, 3)));
+    assert_eq!(upd.lsas_of_type(OspfLsType::Summary).count(), 0);
+}
+
+#[test]
+pub fn display_lsa_header_fields() {
+    let id = Ipv4Addr::new(192, 168, 1, 0);
+    let router_id = Ipv4Addr::new(10, 0, 0, 1);
+    let mut h = OspfLsaHeader::new(OspfLsType::Summary, id, router_id);
+    h.ls_seq_number = 0x1234;
+    h.ls_checksum = 0x0a0b;
+
+    let output = h.to_string();
+    assert!(output.contains("LS ID: 192.168.1.0\n"));
+    assert!(output.contains("Advertising router: 10.0.0.1\n"));
+    assert!(output.contains("LS seq num: 00001234\n"));
+    assert!(output.contains("LS checksum: 0a0b\n"));
+    assert!(!output.contains("LS checksu:"));
+    assert!(!output.contains("2571"));
+}
+
+#[test]
+pub fn parse_min_packet_len() {
+    assert_eq!(HELLO.len(), OSPF_HELLO_MIN_LEN);
+    assert!(parse(HELLO).is_ok());
+
+    let err = parse(&HELLO[..OSPF_MIN_PACKET_LEN - 1]).unwrap_err();
+    assert!(matches!(err, nom::Err::Error(_)));
+    assert_eq!(OspfError::from(err), OspfError::Truncated);
+    assert!(matches!(parse(&[]), Err(nom::Err::Error(_))));
+
+    assert_eq!(OSPF_LSA_HEADER_LEN, 20);
+    assert_eq!(OSPF_MAX_PACKET_LEN, u16::MAX as usize);
+}
+
+#[test]
+pub fn debug_lsa_header() {
+    let id = Ipv4Addr::new(192, 168, 1, 0);
+    let router_id = Ipv4Addr::new(10, 0, 0, 1);
+    let mut h = OspfLsaHeader::new(OspfLsType::Summary, id, router_id);
+    h.ls_age = 100;
+    h.ls_seq_number = 0x80000001;
+    h.ls_checksum = 0x0a0b;
+
+    let output = format!("{:?}", h);
+    assert!(output.starts_with("OspfLsaHeader {"));
+    assert!(output.contains("ls_age: 100s"));
+    assert!(output.contains("ls_type: Summary"));
+    assert!(output.contains("ls_id: 192.168.1.0"));
+    assert!(output.contains("adv_router: 10.0.0.1"));
+    assert!(output.contains("ls_seq_number: 0x80000001"));
+    assert!(output.contains("ls_checksum: 0x0a0b"));
+}
+
+#[test]
+pub fn ls_update_empty() {
+    let router_id = Ipv4Addr::new(10, 0, 0, 1);
+    let upd = OspfLsUpdate {
+        num_adv: 0,
+        lsas: vec![],
+    };
+    let packet = Ospfv2Packet::new(
+        &router_id,
+        &Ipv4Addr::UNSPECIFIED,
+        Ospfv2Payload::LsUpdate(upd),
+    );
+    let buf = packet.emit_to_vec();
+    assert_eq!(buf.len(), 28);
+    assert_eq!(&buf[24..], &[0, 0, 0, 0]);
+
+    let packet = parse_packet(&buf).unwrap();
+    let Ospfv2Payload::LsUpdate(upd) = packet.payload else {
+        panic!("not LS Update");
+    };
+    assert_eq!(upd.num_adv, 0);
+    assert!(upd.lsas.is_empty());
+
+    // The number of LSAs is written from lsas, not from num_adv.
+    let upd = OspfLsUpdate {
+        num_adv: 3,
+        lsas: vec![],
+    };
+    let mut buf = BytesMut::new();
+    upd.emit(&mut buf);
+    assert_eq!(&buf[..], &[0, 0, 0, 0]);
+}
+
+#[test]
+pub fn options_str_flags() {
+    assert_eq!(OspfOptions::new().as_str_flags(), "-|-|-|-|-|-|-|-");
+    assert_eq!(
+        OspfOptions::new().with_external(true).as_str_flags(),
+        "-|-|-|-|-|-|E|-"
+    );
+    assert_eq!(
+        OspfOptions::new()
+            .with_external(true)
+            .with_lls_data(true)
+            .as_str_flags(),
+        "-|-|-|LLS|-|-|E|-"
+    );
+    assert_eq!(
+        OspfOptions::from_bits(0xff).as_str_flags(),
+        "DN|O|DC|LLS|N|MC|E|MT"
+    );
+
+    assert_eq!(DbDescFlags::new().as_str_flags(), "-|-|-|-");
+    assert_eq!(DbDescFlags::initial_master().as_str_flags(), "I|M|MS|-");
+    assert_eq!(DbDescFlags::continuing_slave().as_str_flags(), "-|M|-|-");
+    assert_eq!(DbDescFlags::from_bits(0x0f).as_str_flags(), "I|M|MS|OOB");
+}
+
+#[test]
+pub fn lsa_header_self_originated() {
+    let (_, packet) = parse(DB_DESC_LSA).unwrap();
+    let Ospfv2Payload::DbDesc(dd) = packet.payload else {
+        panic!("not DB Description");
+    };
+    let router_id = Ipv4Addr::new(192, 168, 170, 3);
+    let own: Vec<_> = dd
+        .lsa_headers
+        .iter()
+        .filter(|h| h.is_self_originated(router_id))
+        .collect();
+    assert_eq!(own.len(), 1);
+    assert_eq!(own[0].ls_type, OspfLsType::Router);
+    assert!(dd
+        .lsa_headers
+        .iter()
+        .all(|h| !h.requires_refresh(router_id)));
+
+    let mut h = own[0].clone();
+    h.ls_age = LSA_REFRESH_TIME - 1;
+    assert!(!h.requires_refresh(router_id));
+    h.ls_age = LSA_REFRESH_TIME;
+    assert!(h.requires_refresh(router_id));
+    assert!(!h.requires_refresh(Ipv4Addr::new(192, 168, 170, 2)));
+    h.ls_age = LSA_MAX_AGE;
+    assert!(h.requires_refresh(router_id));
+}
+
+#[test]
+pub fn summary_lsa_tos_round_trip() {
+    let summary = SummaryLsa {
+        netmask: Ipv4Addr::new(255, 255, 255, 0),
+        tos: 0,
+        metric: 30,
+        tos_routes: vec![TosRoute {
+            tos: 8,
+            metric: 0x012345,
+        }],
+    };
+    let lsa = OspfLsa::new(
+        Ipv4Addr::new(4, 4, 4, 4),
+        Ipv4Addr::new(192, 168, 10, 0),
+        SequenceNumber::INITIAL,
+        OspfLsaPayload::Summary(summary.clone()),
+    );
+    let buf = lsa.emit_to_vec();
+    assert_eq!(buf.len(), 20 + 8 + 4);
+    assert_eq!(&buf[20..], &hex!("ff ff ff 00 00 00 00 1e 08 01 23 45"));
+
+    let (rem, parsed) = parse_lsa_strict(&buf).unwrap();
+    assert!(rem.is_empty());
+    assert_eq!(parsed.lsa, OspfLsaPayload::Summary(summary));
+
+    let tos = OspfRouterTOS {
+        tos: 8,
+        resved: 0,
+        metric: 100,
+    };
+    let mut buf = BytesMut::new();
+    tos.emit(&mut buf);
+    assert_eq!(&buf[..], &[8, 0, 0, 100]);
+}
+
+#[test]
+pub fn hello_dr_election() {
+    let dr = Ipv4Addr::new(192, 168, 0, 1);
+    let bdr = Ipv4Addr::new(192, 168, 0, 2);
+    let mut hello = OspfHello::default();
+    assert!(!hello.is_dr_eligible());
+    assert_eq!(hello.declared_dr(), None);
+    assert!(!hello.is_designated_router(dr));
+
+    hello.priority = 1;
+    hello.d_router = dr;
+    hello.bd_router = bdr;
+    assert!(hello.is_dr_eligible());
+    assert_eq!(hello.declared_dr(), Some(dr));
+    assert!(hello.is_designated_router(dr));
+    assert!(!hello.is_designated_router(bdr));
+    assert!(hello.is_backup_dr(bdr));
+    assert!(!hello.is_backup_dr(dr));
+}
+
+#[test]
+pub fn db_desc_master_slave_state() {
+    let master = OspfDbDesc {
+        flags: DbDescFlags::continuing_master(),
+        seqnum: 101,
+        ..Default::default()
+    };
+    assert_eq!(master.assert_master_state(), Ok(()));
+    assert_eq!(
+        master.assert_slave_state(),
+        Err(DbDescError::WrongMasterSlaveBit)
+    );
+
+    let slave = OspfDbDesc {
+        flags: DbDescFlags::continuing_slave(),
+        seqnum: 101,
+        ..Default::default()
+    };
+    assert_eq!(slave.assert_slave_state(), Ok(()));
+    assert_eq!(
+        slave.assert_master_state(),
+        Err(DbDescError::WrongMasterSlaveBit)
+    );
+
+    let mut retransmit = master.clone();
+    retransmit.lsa_headers.push(OspfLsaHeader::default());
+    assert!(retransmit.would_be_retransmit_of(&master));
+    assert!(!slave.would_be_retransmit_of(&master));
+
+    let next = OspfDbDesc {
+        seqnum: 102,
+        ..master.clone()
+    };
+    assert!(!next.would_be_retransmit_of(&master));
+}
+
+#[test]
+pub fn lsa_header_ord() {
+    use std::cmp::Ordering;
+
+    let id = Ipv4Addr::new(10, 0, 0, 1);
+    let mut base = OspfLsaHeader::new(OspfLsType::Router, id, id);
+    base.ls_seq_number = 0x80000002;
+    base.ls_checksum = 0x1000;
+    base.ls_age = 100;
+    assert_eq!(base.cmp(&base.clone()), Ordering::Equal);
+
+    // Sequence number is compared as a signed integer.
+    let mut h = base.clone();
+    h.ls_seq_number = 0x00000001;
+    assert!(h > base);
+    assert!(h.is_newer_than(&base));
+    h.ls_seq_number = 0x80000001;
+    assert!(h < base);
+
+    // Larger checksum is more recent.
+    let mut h = base.clone();
+    h.ls_checksum = 0x2000;
+    h.ls_age = 3000;
+    assert!(h > base);
+
+    // MaxAge is more recent.
+    let mut h = base.clone();
+    h.ls_age = LSA_MAX_AGE;
+    assert!(h > base);
+    assert!(h.is_newer_than(&base));
+
+    // Smaller age is more recent.
+    let mut h = base.clone();
+    h.ls_age = 50;
+    assert!(h > base);
+    h.ls_age = 150;
+    assert!(h < base);
+
+    // Same freshness, ordered by the other fields.
+    let mut h = base.clone();
+    h.adv_router = Ipv4Addr::new(10, 0, 0, 2);
+    assert_ne!(h.cmp(&base), Ordering::Equal);
+    assert_ne!(h, base);
+
+    let mut newer = base.clone();
+    newer.ls_seq_number += 1;
+    assert_eq!(OspfLsaHeader::most_recent(&base, &newer), &newer);
+    assert_eq!(OspfLsaHeader::most_recent(&newer, &base), &newer);
+    assert_eq!(OspfLsaHeader::most_recent(&base, &base.clone()), &base);
+
+    let mut maxage = base.clone();
+    maxage.ls_age = LSA_MAX_AGE;
+    let mut headers = vec![newer.clone(), maxage.clone(), base.clone()];
+    headers.sort();
+    assert_eq!(headers, vec![base, maxage, newer]);
+}
+
+#[test]
+pub fn lsa_header_view() {
+    let (_, packet) = parse(DB_DESC_LSA).unwrap();
+    let Ospfv2Payload::DbDesc(dd) = packet.payload else {
+        panic!("not DB Description");
+    };
+
+    // Scan the LSA headers in place and compare with the parsed headers.
+    let views: Vec<_> = DB_DESC_LSA[32..]
+        .chunks(OSPF_LSA_HEADER_LEN)
+        .map(|chunk| parse_lsa_header_view(chunk).unwrap())
+        .collect();
+    assert_eq!(views.len(), dd.lsa_headers.len());
+    for (view, h) in views.iter().zip(dd.lsa_headers.iter()) {
+        assert_eq!(view.ls_age(), h.ls_age);
+        assert_eq!(view.options(), h.options);
+        assert_eq!(view.ls_type(), h.ls_type);
+        assert_eq!(view.ls_id(), h.ls_id);
+        assert_eq!(view.adv_router(), h.adv_router);
+        assert_eq!(view.ls_seq_number(), h.ls_seq_number);
+        assert_eq!(view.ls_checksum(), h.ls_checksum);
+        assert_eq!(view.length(), h.length);
+        assert_eq!(&view.to_owned_header(), h);
+        assert_eq!(view.as_bytes().len(), OSPF_LSA_HEADER_LEN);
+    }
+
+    assert_eq!(
+        parse_lsa_header_view(&DB_DESC_LSA[32..51]),
+        Err(OspfError::Truncated)
+    );
+}
+
+#[test]
+pub fn parse_ls_update_lazily() {
+    let (_, packet) = parse(LS_UPD_MULTI).unwrap();
+    let Ospfv2Payload::LsUpdate(upd) = packet.payload else {
+        panic!("not LS Update");
+    };
+
+    let mut count = 0;
+    let mut rem: &[u8] = &[];
+    for (res, expected) in
+        parse_ls_update_iter(&LS_UPD_MULTI[28..], upd.num_adv).zip(upd.lsas.iter())
+    {
+        let (r, lsa) = res.unwrap();
+        assert_eq!(&lsa, expected);
+        rem = r;
+        count += 1;
+    }
+    assert_eq!(count, 7);
+    assert!(rem.is_empty());
+    assert_eq!(parse_ls_update_iter(&LS_UPD_MULTI[28..], 7).count(), 7);
+    assert_eq!(parse_ls_update_iter(&LS_UPD_MULTI[28..], 2).count(), 2);
+
+    // Truncated in the middle of the second LSA.
+    let mut iter = parse_ls_update_iter(&LS_UPD_MULTI[28..100], 7);
+    assert!(iter.next().unwrap().is_ok());
+    assert!(iter.next().unwrap().is_err());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+pub fn lsa_byte_len() {
+    let router_id = Ipv4Addr::new(10, 0, 0, 1);
+    let netmask = Ipv4Addr::new(255, 255, 255, 0);
+    let mut router = RouterLsa {
+        links: vec![
+            RouterLsaLink::new_stub(Ipv4Addr::new(10, 0, 1, 0), netmask, 10),
+            RouterLsaLink::new_point_to_point(router_id, router_id, 10),
+        ],
+        ..Default::default()
+    };
+    router.links[1].toses.push(OspfRouterTOS {
+        tos: 8,
+        resved: 0,
+        metric: 20,
+    });
+    router.links[1].num_tos = 1;
+    let payloads: Vec<OspfLsaPayload> = vec![
+        router.into(),
+        NetworkLsa {
+            netmask,
+            attached_routers: vec![router_id, Ipv4Addr::new(10, 0, 0, 2)],
+        }
+        .into(),
+        SummaryLsa {
+            netmask,
+            tos: 0,
+            metric: 10,
+            tos_routes: vec![TosRoute { tos: 8, metric: 20 }],
+        }
+        .into(),
+        AsExternalLsa {
+            netmask,
+            ext_and_resvd: 0x80,
+            metric: 20,
+            forwarding_address: Ipv4Addr::UNSPECIFIED,
+            external_route_tag: 0,
+            tos_list: vec![],
+        }
+        .into(),
+    ];
+    let expected = [20 + 4 + 12 + 16, 20 + 4 + 8, 20 + 8 + 4, 20 + 16];
+
+    let mut lsas = Vec::new();
+    for (payload, len) in payloads.into_iter().zip(expected) {
+        let lsa = OspfLsa::new(router_id, router_id, SequenceNumber::INITIAL, payload);
+        assert_eq!(lsa.byte_len(), len);
+        assert_eq!(lsa.byte_len(), lsa.emit_to_vec().len());
+        lsas.push(lsa);
+    }
+
+    let upd = OspfLsUpdate { num_adv: 4, lsas };
+    let mut buf = BytesMut::new();
+    upd.emit(&mut buf);
+    assert_eq!(upd.byte_len(), buf.len());
+    assert_eq!(upd.byte_len(), 4 + expected.iter().sum::<usize>());
+}
+
+#[test]
+pub fn packet_byte_len() {
+    const PACKETS: &[&[u8]] = &[
+        // Hello with a neighbor.
+        &hex!(
+            "
+            02 01 00 30 0b 00 00 03 00 00 00 00 d9 91 00 00
+            00 00 00 00 00 00 00 00 ff ff ff 00 00 0a 02 01
+            00 00 00 28 0b 00 00 01 0b 00 00 03 01 01 01 01
+            "
+        ),
+        // Database Description with LSA headers.
+        DB_DESC_LSA,
+        // LS Request.
+        &hex!(
+            "
+            02 03 00 24 c0 a8 aa 03 00 00 00 01 bd c7 00 00
+            00 00 00 00 00 00 00 00 00 00 00 01 c0 a8 aa 08
+            c0 a8 aa 08
+            "
+        ),
+        // LS Update.
+        &hex!(
+            "
+            02 04 00 40 c0 a8 aa 08 00 00 00 01 96 1f 00 00
+            00 00 00 00 00 00 00 00 00 00 00 01 03 e2 02 01
+            c0 a8 aa 08 c0 a8 aa 08 80 00 0d c3 25 06 00 24
+            02 00 00 01 c0 a8 aa 00 ff ff ff 00 03 00 00 0a
+            "
+        ),
+    ];
+    for packet in PACKETS {
+        let (_, parsed) = parse(packet).unwrap();
+        assert_eq!(parsed.byte_len(), packet.len());
+        assert_eq!(parsed.byte_len(), parsed.emit_to_vec().len());
+    }
+
+    let (_, packet) = parse(PACKETS[3]).unwrap();
+    let Ospfv2Payload::LsUpdate(ref upd) = packet.payload else {
+        panic!("not LS Update");
+    };
+    let ack = Ospfv2Packet::new(
+        &packet.router_id,
+        &packet.area_id,
+        Ospfv2Payload::LsAck(upd.as_lsa_ack()),
+    );
+    assert_eq!(ack.byte_len(), 24 + 20);
+    assert_eq!(ack.byte_len(), ack.emit_to_vec().len());
+}
+
+#[test]
+pub fn lsa_fletcher_checksum() {
+    const LSA: &[u8] = &hex!(
+        "
+        03 e2 02 01 c0 a8 aa 08 c0 a8 aa 08 80 00 0d c3
+        25 06 00 24 02 00 00 01 c0 a8 aa 00 ff ff ff 00
+        03 00 00 0a
+        "
+    );
+    assert!(verify_fletcher_checksum(LSA));
+
+    let mut lsa = LSA.to_vec();
+    lsa[16] = 0;
+    lsa[17] = 0;
+    assert!(!verify_fletcher_checksum(&lsa));
+    assert_eq!(compute_fletcher_checksum(&lsa), (0x25, 0x06));
+
+    // LS age is not covered by the checksum.
+    lsa[16] = 0x25;
+    lsa[17] = 0x06;
+    lsa[1] = 0xff;
+    assert!(verify_fletcher_checksum(&lsa));
+
+    lsa[30] = 0x01;
+    assert!(!verify_fletcher_checksum(&lsa));
+    assert!(!verify_fletcher_checksum(&LSA[..19]));
+
+    // emit() fills the checksum.
+    let (_, mut lsa) = parse_lsa(LSA).unwrap();
+    lsa.h.ls_checksum = 0;
+    let buf = lsa.emit_to_vec();
+    assert_eq!(&buf[..], LSA);
+    assert!(verify_fletcher_checksum(&buf));
+}
+
+#[test]
+pub fn lsa_sign() {
+    let router_id = Ipv4Addr::new(10, 0, 0, 1);
+    let links = vec![RouterLsaLink::new("10.0.0.0/24".parse().unwrap(), 10)];
+    let mut lsa = OspfLsa::new_router(router_id, SequenceNumber::INITIAL, links);
+    assert_eq!(lsa.h.ls_checksum, 0);
+
+    lsa.sign();
+    assert_eq!(lsa.h.length, 36);
+    assert_ne!(lsa.h.ls_checksum, 0);
+
+    let buf = lsa.emit_to_vec();
+    assert_eq!(buf.len(), lsa.h.length as usize);
+    assert!(lsa.h.validate_checksum(&buf));
+    let (_, parsed) = parse_lsa_strict(&buf).unwrap();
+    assert_eq!(parsed, lsa);
+}
+
+#[test]
+pub fn add_lsa_and_header() {
+    let router_id = Ipv4Addr::new(10, 0, 0, 1);
+    let lsa1 = OspfLsa::new_router(router_id, SequenceNumber::INITIAL, vec![]);
+    let lsa2 = OspfLsa::new(
+        router_id,
+        Ipv4Addr::new(192, 168, 1, 0),
+        SequenceNumber::INITIAL,
+        NetworkLsa {
+            netmask: Ipv4Addr::new(255, 255, 255, 0),
+            attached_routers: vec![router_id],
+        }
+        .into(),
+    );
+
+    let mut dd = OspfDbDesc::default();
+    dd.add_lsa_header(lsa1.h.clone());
+    let dd = dd.with_lsa_header(lsa2.h.clone());
+    assert_eq!(dd.lsa_headers, vec![lsa1.h.clone(), lsa2.h.clone()]);
+    let mut buf = BytesMut::new();
+    dd.emit(&mut buf);
+    assert_eq!(buf.len(), 8 + 2 * 20);
+
+    let mut upd = OspfLsUpdate::default();
+    upd.add_lsa(lsa1.clone());
+    assert_eq!(upd.num_adv, 1);
+    let upd = upd.with_lsa(lsa2.clone());
+    assert_eq!(upd.num_adv, 2);
+    assert_eq!(upd.lsas, vec![lsa1, lsa2]);
+
+    let packet = Ospfv2Packet::new(
+        &router_id,
+        &Ipv4Addr::UNSPECIFIED,
+        Ospfv2Payload::LsUpdate(upd.clone()),
+    );
+    let (_, parsed) = parse(&packet.emit_to_vec()).unwrap();
+    let Ospfv2Payload::LsUpdate(parsed) = parsed.payload else {
+        panic!("not LS Update");
+    };
+    assert_eq!(parsed.num_adv, 2);
+    assert_eq!(parsed.lsas.len(), 2);
+    assert_eq!(parsed.lsas[1].lsa, upd.lsas[1].lsa);
+}
+
+#[test]
+pub fn options_compatibility() {
+    let local = OspfHello {
+        hello_interval: 10,
+        router_dead_interval: 40,
+        options: OspfOptions::new().with_external(true),
+        ..Default::default()
+    };
+
+    // Bits other than E and N may differ.
+    let mut remote = local.clone();
+    remote.options = remote.options.with_lls_data(true).with_o(true);
+    assert_eq!(local.options_error(&remote), None);
+    assert!(local.options_compatible_with(&remote));
+    assert!(local.is_compatible_with(&remote));
+
+    let mut remote = local.clone();
+    remote.options = OspfOptions::new();
+    assert_eq!(
+        local.options_error(&remote),
+        Some(HelloOptionsError::EBitMismatch)
+    );
+    assert!(!local.is_compatible_with(&remote));
+
+    let local = OspfHello {
+        options: OspfOptions::new().with_nssa(true),
+        ..local
+    };
+    let mut remote = local.clone();
+    remote.options = OspfOptions::new();
+    assert_eq!(
+        local.options_error(&remote),
+        Some(HelloOptionsError::NBitMismatch)
+    );
+    assert!(!local.options_compatible_with(&remote));
+    assert_eq!(
+        local.compatibility_error(&remote),
+        Some(HelloCompatibilityError::Options {
+            local: local.options,
+            remote: remote.options
+        })
+    );
+
+    let id = Ipv4Addr::new(10, 0, 0, 1);
+    let mut h1 = OspfLsaHeader::new(OspfLsType::Router, id, id);
+    let mut h2 = h1.clone();
+    h1.options = OspfOptions::new().with_external(true);
+    h2.options = OspfOptions::new().with_external(true).with_o(true);
+    assert!(h1.options_compatible_with(&h2));
+    h2.options = OspfOptions::new();
+    assert!(!h1.options_compatible_with(&h2));
+}
+
+#[derive(Default)]
+struct CountingHandler {
+    hello: usize,
+    db_desc: usize,
+    ls_request: usize,
+    ls_update: usize,
+    ls_ack: usize,
+    unknown: usize,
+}
+
+impl OspfPacketHandler for CountingHandler {
+    fn handle_hello(&mut self, _pkt: &Ospfv2Packet, _hello: &OspfHello) {
+        self.hello += 1;
+    }
+    fn handle_db_desc(&mut self, _pkt: &Ospfv2Packet, _desc: &OspfDbDesc) {
+        self.db_desc += 1;
+    }
+    fn handle_ls_request(&mut self, _pkt: &Ospfv2Packet, _req: &OspfLsRequest) {
+        self.ls_request += 1;
+    }
+    fn handle_ls_update(&mut self, _pkt: &Ospfv2Packet, _update: &OspfLsUpdate) {
+        self.ls_update += 1;
+    }
+    fn handle_ls_ack(&mut self, _pkt: &Ospfv2Packet, _ack: &OspfLsAck) {
+        self.ls_ack += 1;
+    }
+    fn handle_unknown(&mut self, _pkt: &Ospfv2Packet) {
+        self.unknown += 1;
+    }
+}
+
+#[test]
+pub fn dispatch_packet_handler() {
+    const PACKETS: &[&[u8]] = &[
+        HELLO,
+        &hex!(
+            "
+            02 06 00 2c c0 a8 aa 08 00 00 00 01 27 3b 00 00
+            00 00 00 00 00 00 00 00 ff ff ff 00 00 0a 02 01
+            00 00 00 28 c0 a8 aa 08 00 00 00 00
+            "
+        ),
+        &hex!(
+            "
+            02 02 00 20 c0 a8 aa 08 00 00 00 01 a0 52 00 00
+            00 00 00 00 00 00 00 00 05 dc 02 07 41 77 a9 7e
+            "
+        ),
+        &hex!(
+            "
+            02 03 00 24 c0 a8 aa 03 00 00 00 01 bd c7 00 00
+            00 00 00 00 00 00 00 00 00 00 00 01 c0 a8 aa 08
+            c0 a8 aa 08
+            "
+        ),
+        &hex!(
+            "
+            02 05 00 2c c0 a8 aa 08 00 00 00 01 02 f2 00 00
+            00 00 00 00 00 00 00 00 00 01 02 01 c0 a8 aa 03
+            c0 a8 aa 03 80 00 00 02 38 9d 00 30
+            "
+        ),
+    ];
+
+    let mut handler = CountingHandler::default();
+    for input in PACKETS {
+        let (_, packet) = parse(input).unwrap();
+        dispatch(&mut handler, &packet);
+    }
+    let update = Ospfv2Packet::new(
+        &Ipv4Addr::new(1, 1, 1, 1),
+        &Ipv4Addr::UNSPECIFIED,
+        Ospfv2Payload::LsUpdate(OspfLsUpdate::default()),
+    );
+    dispatch(&mut handler, &update);
+
+    assert_eq!(handler.hello, 1);
+    assert_eq!(handler.db_desc, 1);
+    assert_eq!(handler.ls_request, 1);
+    assert_eq!(handler.ls_update, 1);
+    assert_eq!(handler.ls_ack, 1);
+    assert_eq!(handler.unknown, 1);
+}
+
+#[derive(Default)]
+struct RouterIdCollector {
+    router_ids: Vec<Ipv4Addr>,
+    external: usize,
+}
+
+impl OspfLsaVisitor for RouterIdCollector {
+    fn visit_router(&mut self, h: &OspfLsaHeader, _lsa: &RouterLsa) {
+        self.router_ids.push(h.adv_router);
+    }
+    fn visit_as_external(&mut self, _h: &OspfLsaHeader, _lsa: &AsExternalLsa) {
+        self.external += 1;
+    }
+}
+
+#[test]
+pub fn lsa_visitor() {
+    let (_, packet) = parse(LS_UPD_MULTI).unwrap();
+    let Ospfv2Payload::LsUpdate(ref upd) = packet.payload else {
+        panic!("not LS Update");
+    };
+
+    let mut visitor = RouterIdCollector::default();
+    for lsa in upd.lsas.iter() {
+        lsa.accept(&mut visitor);
+    }
+    assert_eq!(visitor.router_ids, vec![Ipv4Addr::new(192, 168, 170, 3)]);
+    assert_eq!(visitor.external, 6);
+}
+
+#[test]
+pub fn lsa_payload_accessors() {
+    let summary = SummaryLsa {
+        netmask: Ipv4Addr::new(255, 255, 255, 0),
+        tos: 0,
+        metric: 10,
+        tos_routes: vec![],
+    };
+    let mut router = OspfLsaPayload::Router(RouterLsa::default());
+    let network = OspfLsaPayload::Network(NetworkLsa {
+        netmask: Ipv4Addr::new(255, 255, 255, 0),
+        attached_routers: vec![Ipv4Addr::new(1, 1, 1, 1)],
+    });
+    let mut summary_net = OspfLsaPayload::Summary(summary.clone());
+    let summary_asbr = OspfLsaPayload::SummaryAsbr(summary.clone());
+    let unknown = OspfLsaPayload::Unknown(UnknownLsa { data: vec![1, 2] });
+
+    assert!(router.as_router().is_some());
+    assert!(router.as_network().is_none());
+    assert!(router.as_unknown().is_none());
+    assert_eq!(network.as_network().unwrap().attached_routers.len(), 1);
+    assert!(network.as_router().is_none());
+    assert_eq!(summary_net.as_summary(), Some(&summary));
+    assert!(summary_net.as_summary_asbr().is_none());
+    assert_eq!(summary_asbr.as_summary_asbr(), Some(&summary));
+    assert!(summary_asbr.as_summary().is_none());
+    assert!(summary_asbr.as_as_external().is_none());
+    assert_eq!(unknown.as_unknown().unwrap().data, vec![1, 2]);
+    assert!(unknown.as_router().is_none());
+
+    summary_net.as_summary_mut().unwrap().metric = 20;
+    assert_eq!(summary_net.as_summary().unwrap().metric, 20);
+    assert!(summary_net.as_router_mut().is_none());
+    router.as_router_mut().unwrap().flags.set_abr(true);
+    assert!(router.as_router().unwrap().flags.abr());
+}
+
+#[test]
+pub fn ls_update_filter_by_type() {
+    let (_, packet) = parse(LS_UPD_MULTI).unwrap();
+    let Ospfv2Payload::LsUpdate(ref upd) = packet.payload else {
+        panic!("not LS Update");
+    };
+
+    let router = upd.filter_by_type(OspfLsType::Router);
+    assert_eq!(router.num_adv, 1);
+    assert_eq!(router.lsas, vec![upd.lsas[0].clone()]);
+
+    let external = upd.filter_by_type(OspfLsType::AsExternal);
+    assert_eq!(external.num_adv, 6);
+    assert!(external
+        .lsas
+        .iter()
+        .all(|lsa| lsa.h.ls_type == OspfLsType::AsExternal));
+
+    let network = upd.filter_by_type(OspfLsType::Network);
+    assert_eq!(network.num_adv, 0);
+    assert!(network.lsas.is_empty());
+
+    let (link, area, as_) = upd.partition_by_scope();
+    assert_eq!(link.num_adv, 0);
+    assert_eq!(area.num_adv, 1);
+    assert_eq!(area.lsas[0].h.ls_type, OspfLsType::Router);
+    assert_eq!(as_.num_adv, 6);
+    assert_eq!(as_, external);
+}
+
+#[test]
+pub fn sign_verify_simple_auth() {
+    const PACKET: &[u8] = &hex!(
+        "
+        02 01 00 2c c0 a8 0a 01 00 00 00 00 67 4a 00 01
+        63 69 73 63 6f 00 00 00 ff ff ff 00 00 0a 02 01
+        00 00 00 28 c0 a8 0a 01 00 00 00 00
+        "
+    );
+    let (_, packet) = parse(PACKET).unwrap();
+    assert!(packet.verify_simple_auth(b"cisco\0\0\0"));
+    assert!(!packet.verify_simple_auth(b"juniper\0"));
+
+    let Ospfv2Payload::Hello(hello) = packet.payload.clone() else {
+        panic!("not Hello");
+    };
+    let mut signed = Ospfv2Packet::new(
+        &packet.router_id,
+        &packet.area_id,
+        Ospfv2Payload::Hello(hello),
+    );
+    assert!(!signed.verify_simple_auth(b"cisco\0\0\0"));
+    signed.sign_simple_auth(b"cisco\0\0\0");
+    assert_eq!(signed.auth_type, 1);
+    assert!(signed.verify_simple_auth(b"cisco\0\0\0"));
+    assert_eq!(&signed.emit_to_vec()[..], PACKET);
+
+    // Round trip through the wire.
+    let buf = signed.emit_to_vec();
+    let (_, parsed) = parse(&buf).unwrap();
+    assert!(parsed.verify_simple_auth(b"cisco\0\0\0"));
+    assert!(!verify_md5(&buf, b"cisco"));
+    assert!(!parsed.verify_md5(&buf, b"cisco"));
+}
+
+#[test]
+pub fn ls_update_lsdb_table() {
+    let (_, packet) = parse(LS_UPD_MULTI).unwrap();
+    let Ospfv2Payload::LsUpdate(ref upd) = packet.payload else {
+        panic!("not LS Update");
+    };
+
+    assert_eq!(
+        upd.lsas[0].h.summary_line(),
+        "Router           192.168.170.3   192.168.170.3   0x80000001  0x3a9c  2"
+    );
+    assert_eq!(
+        upd.lsas[1].h.summary_line(),
+        "AS External      80.212.16.0     192.168.170.2   0x80000001  0x2a49  3"
+    );
+
+    let table = upd.print_lsdb_table();
+    println!("{}", table);
+    assert_eq!(table.lines().count(), 7);
+    assert_eq!(
+        table
+            .lines()
+            .filter(|l| l.starts_with("AS External"))
+            .count(),
+        6
+    );
+}
+
+#[test]
+pub fn ls_update_emit_payload_round_trip() {
+    let (_, packet) = parse(LS_UPD_MULTI).unwrap();
+    let buf = packet.emit_to_vec();
+    let (rem, reparsed) = parse(&buf).unwrap();
+    assert!(rem.is_empty());
+
+    let Ospfv2Payload::LsUpdate(ref upd) = packet.payload else {
+        panic!("not LS Update");
+    };
+    let Ospfv2Payload::LsUpdate(ref reupd) = reparsed.payload else {
+        panic!("not LS Update");
+    };
+    assert_eq!(reupd.lsas.len(), upd.lsas.len());
+    for (a, b) in upd.lsas.iter().zip(reupd.lsas.iter()) {
+        assert_eq!(a.lsa.ls_type(), b.lsa.ls_type());
+        assert_eq!(a.h.length as usize, a.byte_len());
+        assert_eq!(a, b);
+    }
+}
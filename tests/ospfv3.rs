@@ -0,0 +1,36 @@
+use bytes::BytesMut;
+use hex_literal::hex;
+use ospf_packet::v3::*;
+use ospf_packet::*;
+
+#[test]
+pub fn parse_v3_hello() {
+    const PACKET: &[u8] = &hex!(
+        "
+        03 01 00 28 01 01 01 01 00 00 00 00 fb 86 00 00
+        00 00 00 05 01 00 00 13 00 0a 00 28 00 00 00 00
+        00 00 00 00 02 02 02 02
+        "
+    );
+    let (rem, packet) = parse_v3(PACKET).unwrap();
+    assert!(rem.is_empty());
+    println!("{}", packet);
+
+    assert_eq!(packet.version, 3);
+    assert_eq!(packet.typ, OspfType::Hello);
+    assert_eq!(packet.router_id, 0x01010101);
+    assert_eq!(packet.instance_id, 0);
+    let Ospfv3Payload::Hello(ref hello) = packet.payload else {
+        panic!("not Hello");
+    };
+    assert_eq!(hello.interface_id, 5);
+    assert_eq!(hello.priority, 1);
+    assert_eq!(hello.options, 0x13);
+    assert_eq!(hello.hello_interval, 10);
+    assert_eq!(hello.router_dead_interval, 40);
+    assert_eq!(hello.neighbors.len(), 1);
+
+    let mut buf = BytesMut::new();
+    packet.emit(&mut buf);
+    assert_eq!(&buf[..], PACKET);
+}
@@ -0,0 +1,24 @@
+use bytes::BytesMut;
+use ospf_packet::arbitrary::*;
+use ospf_packet::*;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn packet_round_trip(packet in ospfv2_packet()) {
+        let buf = packet.emit_to_vec();
+
+        // Length and checksum are set by emit.
+        let mut expected = packet;
+        expected.len = buf.len() as u16;
+        expected.checksum = u16::from_be_bytes([buf[12], buf[13]]);
+        prop_assert_eq!(parse(&buf), Ok((&[][..], expected)));
+    }
+
+    #[test]
+    fn lsa_header_round_trip(h in any::<OspfLsaHeader>()) {
+        let mut buf = BytesMut::new();
+        h.emit(&mut buf);
+        prop_assert_eq!(parse_lsa_header(&buf), Ok((&[][..], h)));
+    }
+}